@@ -0,0 +1,20 @@
+DROP TABLE guild_settings;
+
+DELETE FROM usage WHERE kind = 'Settings';
+ALTER TYPE usage_kind RENAME TO usage_kind_new;
+CREATE TYPE usage_kind AS ENUM (
+    'TimeDetect',
+    'TimeConvertByAuthor',
+    'TimeConvertByNonAuthor',
+    'Help',
+    'TimezoneCalledUndetected',
+    'TimezoneCalledDetected',
+    'TimezoneSetUndetected',
+    'TimezoneSetDetected',
+    'Date',
+    'Copy',
+    'CurrentTime'
+    );
+ALTER TABLE usage
+    ALTER COLUMN kind TYPE usage_kind USING kind::text::usage_kind;
+DROP TYPE usage_kind_new;
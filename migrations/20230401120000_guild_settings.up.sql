@@ -0,0 +1,7 @@
+CREATE TABLE guild_settings
+(
+    guild_id     BIGINT PRIMARY KEY,
+    repost_pings BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+ALTER TYPE usage_kind ADD VALUE 'Settings';
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE webhooks
+(
+    channel_id BIGINT PRIMARY KEY,
+    webhook_id BIGINT NOT NULL,
+    token      TEXT   NOT NULL
+);
\ No newline at end of file
@@ -0,0 +1,8 @@
+CREATE TABLE converted_messages
+(
+    message_id       BIGINT PRIMARY KEY,
+    author_id        BIGINT NOT NULL,
+    original_content TEXT
+);
+
+ALTER TYPE usage_kind ADD VALUE 'TimeConvertUndo' AFTER 'TimeConvertByNonAuthor';
\ No newline at end of file
@@ -0,0 +1,20 @@
+DELETE FROM usage WHERE kind = 'TimeConvertEdit';
+ALTER TYPE usage_kind RENAME TO usage_kind_new;
+CREATE TYPE usage_kind AS ENUM (
+    'TimeDetect',
+    'TimeConvertByAuthor',
+    'TimeConvertByNonAuthor',
+    'TimeConvertUndo',
+    'Help',
+    'TimezoneCalledUndetected',
+    'TimezoneCalledDetected',
+    'TimezoneSetUndetected',
+    'TimezoneSetDetected',
+    'Date',
+    'Copy',
+    'CurrentTime',
+    'Settings'
+    );
+ALTER TABLE usage
+    ALTER COLUMN kind TYPE usage_kind USING kind::text::usage_kind;
+DROP TYPE usage_kind_new;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'TimeConvertEdit' AFTER 'TimeConvertUndo';
\ No newline at end of file
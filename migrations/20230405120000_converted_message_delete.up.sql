@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'TimeConvertDelete' AFTER 'TimeConvertEdit';
\ No newline at end of file
@@ -0,0 +1,4 @@
+ALTER TABLE guild_settings
+    DROP COLUMN time_resolution;
+
+DROP TYPE time_resolution;
\ No newline at end of file
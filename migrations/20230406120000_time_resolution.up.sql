@@ -0,0 +1,4 @@
+CREATE TYPE time_resolution AS ENUM ('Today', 'Nearest');
+
+ALTER TABLE guild_settings
+    ADD COLUMN time_resolution time_resolution NOT NULL DEFAULT 'Today';
\ No newline at end of file
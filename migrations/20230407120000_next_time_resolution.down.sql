@@ -0,0 +1,36 @@
+ALTER TABLE timezones
+    DROP COLUMN time_resolution;
+
+UPDATE guild_settings SET time_resolution = 'Today' WHERE time_resolution = 'Next';
+ALTER TABLE guild_settings
+    ALTER COLUMN time_resolution DROP DEFAULT;
+ALTER TYPE time_resolution RENAME TO time_resolution_new;
+CREATE TYPE time_resolution AS ENUM ('Today', 'Nearest');
+ALTER TABLE guild_settings
+    ALTER COLUMN time_resolution TYPE time_resolution USING time_resolution::text::time_resolution;
+ALTER TABLE guild_settings
+    ALTER COLUMN time_resolution SET DEFAULT 'Today';
+DROP TYPE time_resolution_new;
+
+DELETE FROM usage WHERE kind = 'Preferences';
+ALTER TYPE usage_kind RENAME TO usage_kind_new;
+CREATE TYPE usage_kind AS ENUM (
+    'TimeDetect',
+    'TimeConvertByAuthor',
+    'TimeConvertByNonAuthor',
+    'TimeConvertUndo',
+    'TimeConvertEdit',
+    'TimeConvertDelete',
+    'Help',
+    'TimezoneCalledUndetected',
+    'TimezoneCalledDetected',
+    'TimezoneSetUndetected',
+    'TimezoneSetDetected',
+    'Date',
+    'Copy',
+    'CurrentTime',
+    'Settings'
+    );
+ALTER TABLE usage
+    ALTER COLUMN kind TYPE usage_kind USING kind::text::usage_kind;
+DROP TYPE usage_kind_new;
\ No newline at end of file
@@ -0,0 +1,6 @@
+ALTER TYPE time_resolution ADD VALUE 'Next';
+
+ALTER TABLE timezones
+    ADD COLUMN time_resolution time_resolution;
+
+ALTER TYPE usage_kind ADD VALUE 'Preferences';
\ No newline at end of file
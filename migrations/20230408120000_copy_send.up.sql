@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'CopySend' AFTER 'Copy';
\ No newline at end of file
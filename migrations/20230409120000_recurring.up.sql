@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'Recurring' AFTER 'CopySend';
\ No newline at end of file
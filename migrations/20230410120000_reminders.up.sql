@@ -0,0 +1,11 @@
+CREATE TABLE reminders
+(
+    user_id    BIGINT                      NOT NULL,
+    guild_id   BIGINT,
+    channel_id BIGINT                      NOT NULL,
+    message_id BIGINT                      NOT NULL,
+    remind_at  TIMESTAMP(0) WITH TIME ZONE NOT NULL,
+    PRIMARY KEY (user_id, message_id, remind_at)
+);
+
+ALTER TYPE usage_kind ADD VALUE 'Reminder' AFTER 'Recurring';
\ No newline at end of file
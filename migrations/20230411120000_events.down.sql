@@ -0,0 +1,30 @@
+DROP TABLE event_rsvps;
+DROP TYPE rsvp_status;
+DROP TABLE events;
+
+DELETE FROM usage WHERE kind IN ('Event', 'EventRsvp');
+ALTER TYPE usage_kind RENAME TO usage_kind_new;
+CREATE TYPE usage_kind AS ENUM (
+    'TimeDetect',
+    'TimeConvertByAuthor',
+    'TimeConvertByNonAuthor',
+    'TimeConvertUndo',
+    'TimeConvertEdit',
+    'TimeConvertDelete',
+    'Help',
+    'TimezoneCalledUndetected',
+    'TimezoneCalledDetected',
+    'TimezoneSetUndetected',
+    'TimezoneSetDetected',
+    'Date',
+    'Copy',
+    'CopySend',
+    'Recurring',
+    'Reminder',
+    'CurrentTime',
+    'Settings',
+    'Preferences'
+    );
+ALTER TABLE usage
+    ALTER COLUMN kind TYPE usage_kind USING kind::text::usage_kind;
+DROP TYPE usage_kind_new;
\ No newline at end of file
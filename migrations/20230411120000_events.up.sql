@@ -0,0 +1,21 @@
+CREATE TABLE events
+(
+    message_id BIGINT PRIMARY KEY,
+    creator_id BIGINT                      NOT NULL,
+    title      TEXT                        NOT NULL,
+    starts_at  TIMESTAMP(0) WITH TIME ZONE NOT NULL,
+    ends_at    TIMESTAMP(0) WITH TIME ZONE
+);
+
+CREATE TYPE rsvp_status AS ENUM ('Going', 'Maybe', 'NotGoing');
+
+CREATE TABLE event_rsvps
+(
+    message_id BIGINT      NOT NULL REFERENCES events ON DELETE CASCADE,
+    user_id    BIGINT      NOT NULL,
+    status     rsvp_status NOT NULL,
+    PRIMARY KEY (message_id, user_id)
+);
+
+ALTER TYPE usage_kind ADD VALUE 'Event' AFTER 'Reminder';
+ALTER TYPE usage_kind ADD VALUE 'EventRsvp' AFTER 'Event';
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'Calendar' AFTER 'EventRsvp';
\ No newline at end of file
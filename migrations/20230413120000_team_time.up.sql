@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'TeamTime' AFTER 'Calendar';
\ No newline at end of file
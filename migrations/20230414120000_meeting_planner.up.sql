@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'MeetingPlanner' AFTER 'TeamTime';
\ No newline at end of file
@@ -0,0 +1,37 @@
+ALTER TABLE timezones
+    DROP COLUMN work_start,
+    DROP COLUMN work_end,
+    DROP COLUMN days_off;
+DROP TYPE days_off;
+
+DELETE FROM usage WHERE kind = 'Available';
+ALTER TYPE usage_kind RENAME TO usage_kind_new;
+CREATE TYPE usage_kind AS ENUM (
+    'TimeDetect',
+    'TimeConvertByAuthor',
+    'TimeConvertByNonAuthor',
+    'TimeConvertUndo',
+    'TimeConvertEdit',
+    'TimeConvertDelete',
+    'Help',
+    'TimezoneCalledUndetected',
+    'TimezoneCalledDetected',
+    'TimezoneSetUndetected',
+    'TimezoneSetDetected',
+    'Date',
+    'Copy',
+    'CopySend',
+    'Recurring',
+    'Reminder',
+    'Event',
+    'EventRsvp',
+    'Calendar',
+    'TeamTime',
+    'MeetingPlanner',
+    'CurrentTime',
+    'Settings',
+    'Preferences'
+    );
+ALTER TABLE usage
+    ALTER COLUMN kind TYPE usage_kind USING kind::text::usage_kind;
+DROP TYPE usage_kind_new;
\ No newline at end of file
@@ -0,0 +1,8 @@
+CREATE TYPE days_off AS ENUM ('SaturdaySunday', 'FridaySaturday', 'Sunday', 'Never');
+
+ALTER TABLE timezones
+    ADD COLUMN work_start SMALLINT,
+    ADD COLUMN work_end   SMALLINT,
+    ADD COLUMN days_off   days_off NOT NULL DEFAULT 'SaturdaySunday';
+
+ALTER TYPE usage_kind ADD VALUE 'Available' AFTER 'MeetingPlanner';
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'CurrentTimeShare' AFTER 'CurrentTime';
\ No newline at end of file
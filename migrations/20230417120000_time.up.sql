@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'Time' AFTER 'CurrentTimeShare';
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'Convert' AFTER 'Time';
\ No newline at end of file
@@ -0,0 +1,12 @@
+CREATE TYPE clock_style AS ENUM ('ChannelNames', 'PinnedMessage');
+
+CREATE TABLE clocks
+(
+    guild_id    BIGINT PRIMARY KEY,
+    style       clock_style NOT NULL,
+    timezones   TEXT[]      NOT NULL,
+    channel_ids BIGINT[]    NOT NULL,
+    message_id  BIGINT
+);
+
+ALTER TYPE usage_kind ADD VALUE 'Clocks' AFTER 'Convert';
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TYPE usage_kind ADD VALUE 'ServerTimezones' AFTER 'Clocks';
\ No newline at end of file
@@ -0,0 +1,8 @@
+ALTER TABLE guild_settings
+    DROP COLUMN birthday_channel_id,
+    DROP COLUMN birthday_message;
+
+ALTER TABLE timezones
+    DROP COLUMN birthday_month,
+    DROP COLUMN birthday_day,
+    DROP COLUMN birthday_announced_year;
\ No newline at end of file
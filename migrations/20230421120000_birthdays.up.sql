@@ -0,0 +1,8 @@
+ALTER TABLE timezones
+    ADD COLUMN birthday_month          SMALLINT,
+    ADD COLUMN birthday_day            SMALLINT,
+    ADD COLUMN birthday_announced_year INT;
+
+ALTER TABLE guild_settings
+    ADD COLUMN birthday_channel_id BIGINT,
+    ADD COLUMN birthday_message    TEXT;
\ No newline at end of file
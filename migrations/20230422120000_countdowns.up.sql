@@ -0,0 +1,11 @@
+CREATE TABLE countdowns
+(
+    message_id     BIGINT PRIMARY KEY,
+    channel_id     BIGINT                      NOT NULL,
+    creator_id     BIGINT                      NOT NULL,
+    title          TEXT                        NOT NULL,
+    ends_at        TIMESTAMP(0) WITH TIME ZONE NOT NULL,
+    next_update_at TIMESTAMP(0) WITH TIME ZONE NOT NULL
+);
+
+ALTER TYPE usage_kind ADD VALUE 'Countdown' AFTER 'ServerTimezones';
\ No newline at end of file
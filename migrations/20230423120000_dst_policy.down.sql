@@ -0,0 +1,4 @@
+ALTER TABLE timezones
+    DROP COLUMN dst_policy;
+
+DROP TYPE dst_policy;
\ No newline at end of file
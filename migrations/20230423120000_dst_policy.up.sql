@@ -0,0 +1,4 @@
+CREATE TYPE dst_policy AS ENUM ('ShiftForward', 'Earliest', 'Latest');
+
+ALTER TABLE timezones
+    ADD COLUMN dst_policy dst_policy NOT NULL DEFAULT 'ShiftForward';
\ No newline at end of file
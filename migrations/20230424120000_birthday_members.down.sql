@@ -0,0 +1 @@
+DROP TABLE birthday_members;
\ No newline at end of file
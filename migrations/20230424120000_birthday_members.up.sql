@@ -0,0 +1,6 @@
+CREATE TABLE birthday_members
+(
+    guild_id BIGINT NOT NULL,
+    user_id  BIGINT NOT NULL,
+    PRIMARY KEY (guild_id, user_id)
+);
\ No newline at end of file
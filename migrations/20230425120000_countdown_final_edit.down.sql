@@ -0,0 +1,2 @@
+ALTER TABLE countdowns
+    DROP COLUMN is_final_edited;
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE countdowns
+    ADD COLUMN is_final_edited BOOLEAN NOT NULL DEFAULT FALSE;
\ No newline at end of file
@@ -0,0 +1,91 @@
+use anyhow::Result;
+use chrono::{Datelike, Duration, Utc};
+use sparkle_convenience::error::ErrorExt;
+use twilight_model::{
+    channel::message::AllowedMentions,
+    id::{marker::UserMarker, Id},
+};
+
+use crate::{database::BirthdayChannel, Context};
+
+pub const DEFAULT_BIRTHDAY_MESSAGE: &str = "happy birthday {user}!! :birthday: :tada:";
+
+impl Context {
+    pub async fn announce_birthdays(&self) -> Result<()> {
+        let now = Utc::now();
+        // everyone's local date is within a day of the utc date
+        let mut months = [now - Duration::days(1), now, now + Duration::days(1)]
+            .iter()
+            .map(|date| i16::try_from(date.month()))
+            .collect::<Result<Vec<_>, _>>()?;
+        months.dedup();
+
+        for user_birthday in self.birthdays_in_months(&months).await? {
+            let today = now.with_timezone(&user_birthday.timezone).date_naive();
+            if !user_birthday.birthday.is_on(today)
+                || user_birthday.announced_year == Some(today.year())
+            {
+                continue;
+            }
+
+            let channels = match self.user_birthday_channels(user_birthday.user_id).await {
+                Ok(channels) => channels,
+                Err(err) => {
+                    self.bot.log(err).await;
+                    continue;
+                }
+            };
+
+            let mut is_failed = false;
+            for channel in channels {
+                if let Err(err) = self
+                    .announce_birthday(user_birthday.user_id, &channel)
+                    .await
+                {
+                    if err.user().is_none() {
+                        self.bot.log(err).await;
+                        is_failed = true;
+                    }
+                }
+            }
+
+            // try again next time if it might work then
+            if is_failed {
+                continue;
+            }
+
+            if let Err(err) = self
+                .set_birthday_announced(user_birthday.user_id, today.year())
+                .await
+            {
+                self.bot.log(err).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn announce_birthday(
+        &self,
+        user_id: Id<UserMarker>,
+        channel: &BirthdayChannel,
+    ) -> Result<()> {
+        self.bot
+            .http
+            .create_message(channel.channel_id)
+            .content(
+                &channel
+                    .message
+                    .as_deref()
+                    .unwrap_or(DEFAULT_BIRTHDAY_MESSAGE)
+                    .replace("{user}", &format!("<@{user_id}>")),
+            )?
+            .allowed_mentions(Some(&AllowedMentions {
+                users: vec![user_id],
+                ..Default::default()
+            }))
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,283 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use sparkle_convenience::error::{extract::HttpErrorExt, ErrorExt, IntoError};
+use twilight_interactions::command::{CommandOption, CreateOption};
+use twilight_model::{
+    channel::{
+        message::Embed,
+        permission_overwrite::{PermissionOverwrite, PermissionOverwriteType},
+        ChannelType,
+    },
+    guild::Permissions,
+    id::{
+        marker::{ChannelMarker, GuildMarker},
+        Id,
+    },
+};
+use twilight_util::builder::embed::EmbedFooterBuilder;
+
+use crate::{
+    database::Clock,
+    embed,
+    message::{UNKNOWN_CHANNEL_ERROR_CODE, UNKNOWN_MESSAGE_ERROR_CODE},
+    time, Context,
+};
+
+pub const MAX_CLOCKS: usize = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CommandOption, CreateOption, sqlx::Type)]
+#[sqlx(type_name = "clock_style")]
+pub enum ClockStyle {
+    #[option(name = "voice channel names", value = "channel_names")]
+    ChannelNames,
+    #[option(name = "a pinned message in this channel", value = "pinned_message")]
+    PinnedMessage,
+}
+
+impl ClockStyle {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::ChannelNames => {
+                "voice channels nobody can join named after the time, updated every 10 minutes \
+                 because discord doesn't let me rename them more often"
+            }
+            Self::PinnedMessage => "a pinned message, updated every minute",
+        }
+    }
+}
+
+pub fn location_name(tz: Tz) -> String {
+    tz.name()
+        .rsplit('/')
+        .next()
+        .unwrap_or_else(|| tz.name())
+        .replace('_', " ")
+}
+
+fn channel_name(tz: Tz, now: DateTime<Utc>) -> String {
+    format!(
+        "🕒 {} {}",
+        location_name(tz),
+        now.with_timezone(&tz).format("%a %-I:%M %p")
+    )
+}
+
+pub fn clock_embed(timezones: &[Tz], now: DateTime<Utc>) -> Result<Embed> {
+    let mut description = String::new();
+    for tz in timezones {
+        writeln!(
+            description,
+            "**{}** {} · {}",
+            location_name(*tz),
+            now.with_timezone(tz).format("%A %-I:%M %p"),
+            time::format_offset(time::utc_offset(*tz, now))
+        )?;
+    }
+
+    Ok(embed()
+        .title("🕒 what time it is around here")
+        .description(description)
+        .footer(EmbedFooterBuilder::new("updated every minute"))
+        .build())
+}
+
+// the channel or message was deleted
+pub fn is_gone(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<twilight_http::Error>()
+            .and_then(HttpErrorExt::code),
+        Some(UNKNOWN_CHANNEL_ERROR_CODE | UNKNOWN_MESSAGE_ERROR_CODE)
+    )
+}
+
+impl Context {
+    pub async fn create_clock(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Id<ChannelMarker>,
+        style: ClockStyle,
+        timezones: Vec<Tz>,
+    ) -> Result<Clock> {
+        let now = Utc::now();
+
+        match style {
+            ClockStyle::ChannelNames => {
+                let mut channel_ids = vec![];
+                for tz in &timezones {
+                    match self.create_clock_channel(guild_id, *tz, now).await {
+                        Ok(channel_id) => channel_ids.push(channel_id),
+                        Err(err) => {
+                            // don't leave the channels made so far behind
+                            for channel_id in channel_ids {
+                                if let Err(delete_err) =
+                                    self.bot.http.delete_channel(channel_id).await
+                                {
+                                    self.bot.log(delete_err).await;
+                                }
+                            }
+                            return Err(err.with_permissions(Permissions::MANAGE_CHANNELS));
+                        }
+                    }
+                }
+
+                Ok(Clock {
+                    guild_id,
+                    style,
+                    timezones,
+                    channel_ids,
+                    message_id: None,
+                })
+            }
+            ClockStyle::PinnedMessage => {
+                let message = async {
+                    let message = self
+                        .bot
+                        .http
+                        .create_message(channel_id)
+                        .embeds(&[clock_embed(&timezones, now)?])?
+                        .await?
+                        .model()
+                        .await?;
+                    if let Err(err) = self.bot.http.create_pin(channel_id, message.id).await {
+                        // an unpinned clock would get lost in the channel
+                        if let Err(delete_err) =
+                            self.bot.http.delete_message(channel_id, message.id).await
+                        {
+                            self.bot.log(delete_err).await;
+                        }
+                        return Err(err.into());
+                    }
+                    Ok::<_, anyhow::Error>(message)
+                }
+                .await
+                .map_err(|err| {
+                    err.with_permissions(
+                        Permissions::SEND_MESSAGES
+                            | Permissions::EMBED_LINKS
+                            | Permissions::MANAGE_MESSAGES,
+                    )
+                })?;
+
+                Ok(Clock {
+                    guild_id,
+                    style,
+                    timezones,
+                    channel_ids: vec![channel_id],
+                    message_id: Some(message.id),
+                })
+            }
+        }
+    }
+
+    async fn create_clock_channel(
+        &self,
+        guild_id: Id<GuildMarker>,
+        tz: Tz,
+        now: DateTime<Utc>,
+    ) -> Result<Id<ChannelMarker>> {
+        Ok(self
+            .bot
+            .http
+            .create_guild_channel(guild_id, &channel_name(tz, now))?
+            .kind(ChannelType::GuildVoice)
+            .permission_overwrites(&[PermissionOverwrite {
+                allow: Permissions::empty(),
+                deny: Permissions::CONNECT,
+                id: guild_id.cast(),
+                kind: PermissionOverwriteType::Role,
+            }])
+            .await?
+            .model()
+            .await?
+            .id)
+    }
+
+    pub async fn remove_clock(&self, clock: &Clock) -> Result<()> {
+        self.remove_clock_display(clock).await?;
+        self.delete_clock(clock.guild_id).await
+    }
+
+    // deletes the channels or message of the clock but keeps it saved
+    pub async fn remove_clock_display(&self, clock: &Clock) -> Result<()> {
+        let result = async {
+            match clock.style {
+                ClockStyle::ChannelNames => {
+                    for channel_id in &clock.channel_ids {
+                        // keep going so one deleted channel doesn't leave the rest behind
+                        if let Err(err) = self.bot.http.delete_channel(*channel_id).await {
+                            let err = anyhow::Error::from(err);
+                            if !is_gone(&err) {
+                                return Err(err);
+                            }
+                        }
+                    }
+                }
+                ClockStyle::PinnedMessage => {
+                    self.bot
+                        .http
+                        .delete_message(*clock.channel_ids.first().ok()?, clock.message_id.ok()?)
+                        .await?;
+                }
+            }
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+
+        match result {
+            Err(err) if !is_gone(&err) => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn update_clock_messages(&self) -> Result<()> {
+        let now = Utc::now();
+        for clock in self.clocks(ClockStyle::PinnedMessage).await? {
+            let result = async {
+                self.bot
+                    .http
+                    .update_message(*clock.channel_ids.first().ok()?, clock.message_id.ok()?)
+                    .embeds(Some(&[clock_embed(&clock.timezones, now)?]))?
+                    .await?;
+                Ok(())
+            }
+            .await;
+            self.handle_clock_update_result(&clock, result).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn update_clock_channels(&self) -> Result<()> {
+        let now = Utc::now();
+        for clock in self.clocks(ClockStyle::ChannelNames).await? {
+            let result = async {
+                for (channel_id, tz) in clock.channel_ids.iter().zip(&clock.timezones) {
+                    self.bot
+                        .http
+                        .update_channel(*channel_id)
+                        .name(&channel_name(*tz, now))?
+                        .await?;
+                }
+                Ok(())
+            }
+            .await;
+            self.handle_clock_update_result(&clock, result).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn handle_clock_update_result(&self, clock: &Clock, result: Result<()>) -> Result<()> {
+        match result {
+            Err(err) if is_gone(&err) => self.delete_clock(clock.guild_id).await,
+            Err(err) => {
+                self.bot.log(err).await;
+                Ok(())
+            }
+            Ok(()) => Ok(()),
+        }
+    }
+}
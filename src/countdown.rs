@@ -0,0 +1,197 @@
+use anyhow::Result;
+use chrono::Utc;
+use twilight_model::channel::message::{AllowedMentions, Embed};
+use twilight_util::builder::embed::EmbedFooterBuilder;
+
+use crate::{clock::is_gone, database::Countdown, embed, Context};
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+pub const fn next_update(ends_at: i64, now: i64) -> i64 {
+    let remaining = ends_at - now;
+    if remaining <= 0 {
+        return ends_at;
+    }
+
+    let interval = if remaining > DAY {
+        DAY
+    } else if remaining > HOUR {
+        HOUR
+    } else {
+        MINUTE
+    };
+
+    // update again when what's left is a round number of the interval
+    ends_at - (remaining - 1) / interval * interval
+}
+
+fn format_remaining(seconds: i64) -> String {
+    let minutes = (seconds + MINUTE / 2) / MINUTE;
+    let parts = [
+        (minutes / (DAY / MINUTE), "day"),
+        (minutes % (DAY / MINUTE) / (HOUR / MINUTE), "hour"),
+        (minutes % (HOUR / MINUTE), "minute"),
+    ]
+    .into_iter()
+    .filter(|(amount, _)| *amount > 0)
+    .map(|(amount, unit)| {
+        if amount == 1 {
+            format!("{amount} {unit}")
+        } else {
+            format!("{amount} {unit}s")
+        }
+    })
+    .collect::<Vec<_>>();
+
+    if parts.is_empty() {
+        "less than a minute".to_owned()
+    } else {
+        parts.join(", ")
+    }
+}
+
+pub fn countdown_embed(title: &str, ends_at: i64, now: i64) -> Embed {
+    if ends_at <= now {
+        return embed()
+            .title(format!("🎉 {title}"))
+            .description(format!("it's time! it was <t:{ends_at}:F>"))
+            .build();
+    }
+
+    embed()
+        .title(format!("⏳ {title}"))
+        .description(format!(
+            "**{}** to go\nuntil <t:{ends_at}:F>",
+            format_remaining(ends_at - now)
+        ))
+        .footer(EmbedFooterBuilder::new(
+            "i update this every day, then every hour, then every minute in the last hour",
+        ))
+        .build()
+}
+
+impl Context {
+    pub async fn update_due_countdowns(&self) -> Result<()> {
+        let now = Utc::now().timestamp();
+        for countdown in self.due_countdowns().await? {
+            let result = if countdown.ends_at <= now {
+                self.finish_countdown(&countdown, now).await
+            } else {
+                self.update_countdown(&countdown, now).await
+            };
+
+            match result {
+                Err(err) if is_gone(&err) => self.delete_countdown(countdown.message_id).await?,
+                // keep it to try again next time
+                Err(err) => self.bot.log(err).await,
+                Ok(()) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn update_countdown(&self, countdown: &Countdown, now: i64) -> Result<()> {
+        self.edit_countdown(countdown, now).await?;
+        self.set_countdown_next_update(countdown.message_id, next_update(countdown.ends_at, now))
+            .await
+    }
+
+    async fn finish_countdown(&self, countdown: &Countdown, now: i64) -> Result<()> {
+        // so a failed completion message doesn't edit the countdown again
+        if !countdown.is_final_edited {
+            self.edit_countdown(countdown, now).await?;
+            self.set_countdown_final_edited(countdown.message_id)
+                .await?;
+        }
+
+        self.bot
+            .http
+            .create_message(countdown.channel_id)
+            .content(&format!(
+                "<@{}> **{}** is here! :tada:",
+                countdown.creator_id, countdown.title
+            ))?
+            .reply(countdown.message_id)
+            .fail_if_not_exists(false)
+            .allowed_mentions(Some(&AllowedMentions {
+                users: vec![countdown.creator_id],
+                ..Default::default()
+            }))
+            .await?;
+
+        self.delete_countdown(countdown.message_id).await
+    }
+
+    async fn edit_countdown(&self, countdown: &Countdown, now: i64) -> Result<()> {
+        self.bot
+            .http
+            .update_message(countdown.channel_id, countdown.message_id)
+            .embeds(Some(&[countdown_embed(
+                &countdown.title,
+                countdown.ends_at,
+                now,
+            )]))?
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENDS_AT: i64 = 1_700_000_000;
+
+    #[test]
+    fn next_update_daily() {
+        let now = ENDS_AT - 3 * DAY - 5 * MINUTE;
+        assert_eq!(next_update(ENDS_AT, now), ENDS_AT - 3 * DAY);
+        assert_eq!(
+            next_update(ENDS_AT, ENDS_AT - DAY - HOUR / 2),
+            ENDS_AT - DAY
+        );
+    }
+
+    #[test]
+    fn next_update_day_to_hour() {
+        assert_eq!(next_update(ENDS_AT, ENDS_AT - DAY), ENDS_AT - 23 * HOUR);
+        assert_eq!(next_update(ENDS_AT, ENDS_AT - DAY + 1), ENDS_AT - 23 * HOUR);
+    }
+
+    #[test]
+    fn next_update_hour_to_minute() {
+        assert_eq!(next_update(ENDS_AT, ENDS_AT - HOUR - 1), ENDS_AT - HOUR);
+        assert_eq!(next_update(ENDS_AT, ENDS_AT - HOUR), ENDS_AT - 59 * MINUTE);
+        assert_eq!(next_update(ENDS_AT, ENDS_AT - 30), ENDS_AT);
+    }
+
+    #[test]
+    fn next_update_done() {
+        assert_eq!(next_update(ENDS_AT, ENDS_AT), ENDS_AT);
+        assert_eq!(next_update(ENDS_AT, ENDS_AT + DAY), ENDS_AT);
+    }
+
+    #[test]
+    fn format_remaining_units() {
+        assert_eq!(
+            format_remaining(DAY + HOUR + MINUTE + 1),
+            "1 day, 1 hour, 1 minute"
+        );
+        assert_eq!(format_remaining(2 * DAY), "2 days");
+        assert_eq!(
+            format_remaining(3 * HOUR + 20 * MINUTE),
+            "3 hours, 20 minutes"
+        );
+    }
+
+    #[test]
+    fn format_remaining_rounds() {
+        assert_eq!(format_remaining(29), "less than a minute");
+        assert_eq!(format_remaining(30), "1 minute");
+        assert_eq!(format_remaining(HOUR - 1), "1 hour");
+    }
+}
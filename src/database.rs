@@ -3,10 +3,17 @@
 use anyhow::Result;
 use chrono_tz::Tz;
 use sparkle_convenience::error::IntoError;
-use sqlx::{query, query_scalar, Postgres};
-use twilight_model::id::{marker::UserMarker, Id};
+use sqlx::{query, query_as, query_scalar, Postgres};
+use twilight_model::id::{
+    marker::{ChannelMarker, GuildMarker, MessageMarker, UserMarker, WebhookMarker},
+    Id,
+};
 
-use crate::{Context, Error};
+use crate::{
+    clock::ClockStyle,
+    time::{Birthday, DaysOff, DstPolicy, TimeResolution, WorkingHours},
+    Context, CustomError, Error,
+};
 
 trait Encode<'a, T: sqlx::Encode<'a, Postgres>> {
     fn encode(&self) -> T;
@@ -29,6 +36,13 @@ trait Decode<T> {
     fn decode(&self) -> T;
 }
 
+impl<T> Decode<Id<T>> for i64 {
+    #[allow(clippy::cast_sign_loss)]
+    fn decode(&self) -> Id<T> {
+        Id::new(*self as u64)
+    }
+}
+
 impl Decode<Result<Tz>> for String {
     #[allow(clippy::use_self)]
     fn decode(&self) -> Result<Tz> {
@@ -42,6 +56,9 @@ pub enum UsageKind {
     TimeDetect,
     TimeConvertByAuthor,
     TimeConvertByNonAuthor,
+    TimeConvertUndo,
+    TimeConvertEdit,
+    TimeConvertDelete,
     Help,
     TimezoneCalledDetected,
     TimezoneCalledUndetected,
@@ -49,7 +66,107 @@ pub enum UsageKind {
     TimezoneSetUndetected,
     Date,
     Copy,
+    CopySend,
+    Recurring,
+    Reminder,
+    Event,
+    EventRsvp,
+    Calendar,
+    TeamTime,
+    MeetingPlanner,
+    Available,
     CurrentTime,
+    CurrentTimeShare,
+    Time,
+    Convert,
+    Clocks,
+    ServerTimezones,
+    Countdown,
+    Settings,
+    Preferences,
+}
+
+#[derive(Clone, Debug)]
+pub struct Webhook {
+    pub id: Id<WebhookMarker>,
+    pub token: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ConvertedMessage {
+    pub author_id: Id<UserMarker>,
+    pub original_content: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GuildSettings {
+    pub repost_pings: bool,
+    pub time_resolution: TimeResolution,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Reminder {
+    pub user_id: Id<UserMarker>,
+    pub guild_id: Option<Id<GuildMarker>>,
+    pub channel_id: Id<ChannelMarker>,
+    pub message_id: Id<MessageMarker>,
+    pub remind_at: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Event {
+    pub creator_id: Id<UserMarker>,
+    pub title: String,
+    pub starts_at: i64,
+    pub ends_at: Option<i64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "rsvp_status")]
+pub enum RsvpStatus {
+    Going,
+    Maybe,
+    NotGoing,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Rsvp {
+    pub user_id: Id<UserMarker>,
+    pub status: RsvpStatus,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct UserBirthday {
+    pub user_id: Id<UserMarker>,
+    pub timezone: Tz,
+    pub birthday: Birthday,
+    pub announced_year: Option<i32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BirthdayChannel {
+    pub guild_id: Id<GuildMarker>,
+    pub channel_id: Id<ChannelMarker>,
+    pub message: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Countdown {
+    pub message_id: Id<MessageMarker>,
+    pub channel_id: Id<ChannelMarker>,
+    pub creator_id: Id<UserMarker>,
+    pub title: String,
+    pub ends_at: i64,
+    pub is_final_edited: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct Clock {
+    pub guild_id: Id<GuildMarker>,
+    pub style: ClockStyle,
+    pub timezones: Vec<Tz>,
+    pub channel_ids: Vec<Id<ChannelMarker>>,
+    pub message_id: Option<Id<MessageMarker>>,
 }
 
 impl Context {
@@ -79,6 +196,797 @@ impl Context {
         }
     }
 
+    pub async fn timezones(
+        &self,
+        user_ids: &[Id<UserMarker>],
+    ) -> Result<Vec<(Id<UserMarker>, Tz)>> {
+        query!(
+            "SELECT user_id, timezone FROM timezones WHERE user_id = ANY($1)",
+            &user_ids.iter().map(Encode::encode).collect::<Vec<i64>>()
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|row| Ok((row.user_id.decode(), row.timezone.decode()?)))
+        .collect()
+    }
+
+    pub async fn webhook(&self, channel_id: Id<ChannelMarker>) -> Result<Option<Webhook>> {
+        Ok(query!(
+            "SELECT webhook_id, token FROM webhooks WHERE channel_id = $1",
+            channel_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .map(|webhook| Webhook {
+            id: webhook.webhook_id.decode(),
+            token: webhook.token,
+        }))
+    }
+
+    pub async fn insert_webhook(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        webhook: &Webhook,
+    ) -> Result<()> {
+        query!(
+            "INSERT INTO webhooks (channel_id, webhook_id, token) VALUES ($1, $2, $3) ON CONFLICT \
+             (channel_id) DO UPDATE SET webhook_id = $2, token = $3",
+            channel_id.encode(),
+            webhook.id.encode(),
+            webhook.token
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_webhook(&self, channel_id: Id<ChannelMarker>) -> Result<()> {
+        query!(
+            "DELETE FROM webhooks WHERE channel_id = $1",
+            channel_id.encode()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn converted_message(
+        &self,
+        message_id: Id<MessageMarker>,
+    ) -> Result<Option<ConvertedMessage>> {
+        Ok(query!(
+            "SELECT author_id, original_content FROM converted_messages WHERE message_id = $1",
+            message_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .map(|converted_message| ConvertedMessage {
+            author_id: converted_message.author_id.decode(),
+            original_content: converted_message.original_content,
+        }))
+    }
+
+    pub async fn insert_converted_message(
+        &self,
+        message_id: Id<MessageMarker>,
+        author_id: Id<UserMarker>,
+        original_content: Option<&str>,
+    ) -> Result<()> {
+        query!(
+            "INSERT INTO converted_messages (message_id, author_id, original_content) VALUES ($1, \
+             $2, $3)",
+            message_id.encode(),
+            author_id.encode(),
+            original_content
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn clear_original_content(&self, message_id: Id<MessageMarker>) -> Result<()> {
+        query!(
+            "UPDATE converted_messages SET original_content = NULL WHERE message_id = $1",
+            message_id.encode()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn clear_all_original_contents(&self) -> Result<()> {
+        query!("UPDATE converted_messages SET original_content = NULL")
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_converted_message(&self, message_id: Id<MessageMarker>) -> Result<()> {
+        query!(
+            "DELETE FROM converted_messages WHERE message_id = $1",
+            message_id.encode()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn guild_settings(&self, guild_id: Id<GuildMarker>) -> Result<GuildSettings> {
+        Ok(query_as!(
+            GuildSettings,
+            r#"
+            SELECT repost_pings, time_resolution AS "time_resolution: _"
+            FROM guild_settings
+            WHERE guild_id = $1"#,
+            guild_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .unwrap_or_default())
+    }
+
+    pub async fn set_repost_pings(
+        &self,
+        guild_id: Id<GuildMarker>,
+        repost_pings: bool,
+    ) -> Result<()> {
+        query!(
+            "INSERT INTO guild_settings (guild_id, repost_pings) VALUES ($1, $2) ON CONFLICT \
+             (guild_id) DO UPDATE SET repost_pings = $2",
+            guild_id.encode(),
+            repost_pings
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_time_resolution(
+        &self,
+        guild_id: Id<GuildMarker>,
+        time_resolution: TimeResolution,
+    ) -> Result<()> {
+        query!(
+            "INSERT INTO guild_settings (guild_id, time_resolution) VALUES ($1, $2) ON CONFLICT \
+             (guild_id) DO UPDATE SET time_resolution = $2",
+            guild_id.encode(),
+            time_resolution as _
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn user_time_resolution(
+        &self,
+        user_id: Id<UserMarker>,
+    ) -> Result<Option<TimeResolution>> {
+        Ok(query_scalar!(
+            r#"SELECT time_resolution AS "time_resolution: _" FROM timezones WHERE user_id = $1"#,
+            user_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .flatten())
+    }
+
+    pub async fn user_dst_policy(&self, user_id: Id<UserMarker>) -> Result<DstPolicy> {
+        Ok(query_scalar!(
+            r#"SELECT dst_policy AS "dst_policy: _" FROM timezones WHERE user_id = $1"#,
+            user_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .unwrap_or_default())
+    }
+
+    pub async fn set_user_dst_policy(
+        &self,
+        user_id: Id<UserMarker>,
+        dst_policy: DstPolicy,
+    ) -> Result<()> {
+        let result = query!(
+            "UPDATE timezones SET dst_policy = $2 WHERE user_id = $1",
+            user_id.encode(),
+            dst_policy as _
+        )
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(CustomError::MissingTimezone(self.command_ids.timezone).into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn set_user_time_resolution(
+        &self,
+        user_id: Id<UserMarker>,
+        time_resolution: Option<TimeResolution>,
+    ) -> Result<()> {
+        let result = query!(
+            "UPDATE timezones SET time_resolution = $2 WHERE user_id = $1",
+            user_id.encode(),
+            time_resolution as _
+        )
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(CustomError::MissingTimezone(self.command_ids.timezone).into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn working_hours(&self, user_id: Id<UserMarker>) -> Result<Option<WorkingHours>> {
+        let Some(row) = query!(
+            r#"SELECT work_start, work_end, days_off AS "days_off: DaysOff" FROM timezones WHERE user_id = $1"#,
+            user_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let (Some(start), Some(end)) = (row.work_start, row.work_end) else {
+            return Ok(None);
+        };
+        Ok(Some(WorkingHours {
+            start: start.try_into()?,
+            end: end.try_into()?,
+            days_off: row.days_off,
+        }))
+    }
+
+    pub async fn working_hours_of(
+        &self,
+        user_ids: &[Id<UserMarker>],
+    ) -> Result<Vec<(Id<UserMarker>, Tz, WorkingHours)>> {
+        query!(
+            r#"
+            SELECT user_id, timezone, work_start AS "work_start!", work_end AS "work_end!",
+                days_off AS "days_off: DaysOff"
+            FROM timezones
+            WHERE user_id = ANY($1) AND work_start IS NOT NULL AND work_end IS NOT NULL"#,
+            &user_ids.iter().map(Encode::encode).collect::<Vec<i64>>()
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok((
+                row.user_id.decode(),
+                row.timezone.decode()?,
+                WorkingHours {
+                    start: row.work_start.try_into()?,
+                    end: row.work_end.try_into()?,
+                    days_off: row.days_off,
+                },
+            ))
+        })
+        .collect()
+    }
+
+    pub async fn set_working_hours(
+        &self,
+        user_id: Id<UserMarker>,
+        start: Option<i16>,
+        end: Option<i16>,
+        days_off: Option<DaysOff>,
+    ) -> Result<()> {
+        let result = query!(
+            "UPDATE timezones SET work_start = COALESCE($2, work_start), work_end = COALESCE($3, \
+             work_end), days_off = COALESCE($4, days_off) WHERE user_id = $1",
+            user_id.encode(),
+            start,
+            end,
+            days_off as _
+        )
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(CustomError::MissingTimezone(self.command_ids.timezone).into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn birthday(&self, user_id: Id<UserMarker>) -> Result<Option<Birthday>> {
+        let Some(row) = query!(
+            "SELECT birthday_month, birthday_day FROM timezones WHERE user_id = $1",
+            user_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        else {
+            return Ok(None);
+        };
+
+        let (Some(month), Some(day)) = (row.birthday_month, row.birthday_day) else {
+            return Ok(None);
+        };
+        Ok(Some(Birthday {
+            month: month.try_into()?,
+            day: day.try_into()?,
+        }))
+    }
+
+    pub async fn set_birthday(
+        &self,
+        user_id: Id<UserMarker>,
+        birthday: Option<Birthday>,
+    ) -> Result<()> {
+        let result = query!(
+            "UPDATE timezones SET birthday_month = $2, birthday_day = $3 WHERE user_id = $1",
+            user_id.encode(),
+            birthday
+                .map(|birthday| i16::try_from(birthday.month))
+                .transpose()?,
+            birthday
+                .map(|birthday| i16::try_from(birthday.day))
+                .transpose()?
+        )
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(CustomError::MissingTimezone(self.command_ids.timezone).into());
+        }
+
+        Ok(())
+    }
+
+    pub async fn birthdays_in_months(&self, months: &[i16]) -> Result<Vec<UserBirthday>> {
+        query!(
+            r#"
+            SELECT user_id, timezone, birthday_month AS "birthday_month!",
+                birthday_day AS "birthday_day!", birthday_announced_year
+            FROM timezones
+            WHERE birthday_month = ANY($1) AND birthday_day IS NOT NULL"#,
+            months
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|row| {
+            Ok(UserBirthday {
+                user_id: row.user_id.decode(),
+                timezone: row.timezone.decode()?,
+                birthday: Birthday {
+                    month: row.birthday_month.try_into()?,
+                    day: row.birthday_day.try_into()?,
+                },
+                announced_year: row.birthday_announced_year,
+            })
+        })
+        .collect()
+    }
+
+    pub async fn set_birthday_announced(&self, user_id: Id<UserMarker>, year: i32) -> Result<()> {
+        query!(
+            "UPDATE timezones SET birthday_announced_year = $2 WHERE user_id = $1",
+            user_id.encode(),
+            year
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn birthday_channel(
+        &self,
+        guild_id: Id<GuildMarker>,
+    ) -> Result<Option<BirthdayChannel>> {
+        Ok(query!(
+            r#"
+            SELECT guild_id, birthday_channel_id AS "birthday_channel_id!", birthday_message
+            FROM guild_settings
+            WHERE guild_id = $1 AND birthday_channel_id IS NOT NULL"#,
+            guild_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .map(|row| BirthdayChannel {
+            guild_id: row.guild_id.decode(),
+            channel_id: row.birthday_channel_id.decode(),
+            message: row.birthday_message,
+        }))
+    }
+
+    pub async fn user_birthday_channels(
+        &self,
+        user_id: Id<UserMarker>,
+    ) -> Result<Vec<BirthdayChannel>> {
+        Ok(query!(
+            r#"
+            SELECT guild_id, birthday_channel_id AS "birthday_channel_id!", birthday_message
+            FROM birthday_members
+                JOIN guild_settings USING (guild_id)
+            WHERE user_id = $1 AND birthday_channel_id IS NOT NULL"#,
+            user_id.encode()
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|row| BirthdayChannel {
+            guild_id: row.guild_id.decode(),
+            channel_id: row.birthday_channel_id.decode(),
+            message: row.birthday_message,
+        })
+        .collect())
+    }
+
+    pub async fn is_birthday_member(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<bool> {
+        Ok(query!(
+            "SELECT FROM birthday_members WHERE guild_id = $1 AND user_id = $2",
+            guild_id.encode(),
+            user_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .is_some())
+    }
+
+    pub async fn set_birthday_member(
+        &self,
+        guild_id: Id<GuildMarker>,
+        user_id: Id<UserMarker>,
+        is_member: bool,
+    ) -> Result<()> {
+        if is_member {
+            query!(
+                "INSERT INTO birthday_members (guild_id, user_id) VALUES ($1, $2) ON CONFLICT DO \
+                 NOTHING",
+                guild_id.encode(),
+                user_id.encode()
+            )
+            .execute(&self.db)
+            .await?;
+        } else {
+            query!(
+                "DELETE FROM birthday_members WHERE guild_id = $1 AND user_id = $2",
+                guild_id.encode(),
+                user_id.encode()
+            )
+            .execute(&self.db)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn set_birthday_channel(
+        &self,
+        guild_id: Id<GuildMarker>,
+        channel_id: Option<Id<ChannelMarker>>,
+    ) -> Result<()> {
+        query!(
+            "INSERT INTO guild_settings (guild_id, birthday_channel_id) VALUES ($1, $2) ON \
+             CONFLICT (guild_id) DO UPDATE SET birthday_channel_id = $2",
+            guild_id.encode(),
+            channel_id.as_ref().map(Encode::encode)
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_birthday_message(
+        &self,
+        guild_id: Id<GuildMarker>,
+        message: &str,
+    ) -> Result<()> {
+        query!(
+            "INSERT INTO guild_settings (guild_id, birthday_message) VALUES ($1, $2) ON CONFLICT \
+             (guild_id) DO UPDATE SET birthday_message = $2",
+            guild_id.encode(),
+            message
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn insert_reminder(&self, reminder: Reminder) -> Result<()> {
+        query!(
+            "INSERT INTO reminders (user_id, guild_id, channel_id, message_id, remind_at) VALUES \
+             ($1, $2, $3, $4, to_timestamp($5::BIGINT)) ON CONFLICT DO NOTHING",
+            reminder.user_id.encode(),
+            reminder.guild_id.as_ref().map(Encode::encode),
+            reminder.channel_id.encode(),
+            reminder.message_id.encode(),
+            reminder.remind_at
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn due_reminders(&self) -> Result<Vec<Reminder>> {
+        Ok(query!(
+            r#"
+            SELECT user_id, guild_id, channel_id, message_id,
+                extract(epoch FROM remind_at)::BIGINT AS "remind_at!"
+            FROM reminders
+            WHERE remind_at <= now()"#
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|reminder| Reminder {
+            user_id: reminder.user_id.decode(),
+            guild_id: reminder.guild_id.as_ref().map(Decode::decode),
+            channel_id: reminder.channel_id.decode(),
+            message_id: reminder.message_id.decode(),
+            remind_at: reminder.remind_at,
+        })
+        .collect())
+    }
+
+    pub async fn delete_reminder(&self, reminder: Reminder) -> Result<()> {
+        query!(
+            "DELETE FROM reminders WHERE user_id = $1 AND message_id = $2 AND remind_at = \
+             to_timestamp($3::BIGINT)",
+            reminder.user_id.encode(),
+            reminder.message_id.encode(),
+            reminder.remind_at
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn insert_event(&self, message_id: Id<MessageMarker>, event: &Event) -> Result<()> {
+        query!(
+            "INSERT INTO events (message_id, creator_id, title, starts_at, ends_at) VALUES ($1, $2, \
+             $3, to_timestamp($4::BIGINT), to_timestamp($5::BIGINT))",
+            message_id.encode(),
+            event.creator_id.encode(),
+            event.title,
+            event.starts_at,
+            event.ends_at
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn event(&self, message_id: Id<MessageMarker>) -> Result<Option<Event>> {
+        Ok(query!(
+            r#"
+            SELECT creator_id, title,
+                extract(epoch FROM starts_at)::BIGINT AS "starts_at!",
+                extract(epoch FROM ends_at)::BIGINT AS ends_at
+            FROM events
+            WHERE message_id = $1"#,
+            message_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .map(|event| Event {
+            creator_id: event.creator_id.decode(),
+            title: event.title,
+            starts_at: event.starts_at,
+            ends_at: event.ends_at,
+        }))
+    }
+
+    pub async fn set_rsvp(&self, message_id: Id<MessageMarker>, rsvp: Rsvp) -> Result<()> {
+        query!(
+            "INSERT INTO event_rsvps (message_id, user_id, status) VALUES ($1, $2, $3) ON CONFLICT \
+             (message_id, user_id) DO UPDATE SET status = $3",
+            message_id.encode(),
+            rsvp.user_id.encode(),
+            rsvp.status as _
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn rsvps(&self, message_id: Id<MessageMarker>) -> Result<Vec<Rsvp>> {
+        Ok(query!(
+            r#"SELECT user_id, status AS "status: RsvpStatus" FROM event_rsvps WHERE message_id = $1"#,
+            message_id.encode()
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|rsvp| Rsvp {
+            user_id: rsvp.user_id.decode(),
+            status: rsvp.status,
+        })
+        .collect())
+    }
+
+    pub async fn insert_countdown(&self, countdown: &Countdown, next_update_at: i64) -> Result<()> {
+        query!(
+            "INSERT INTO countdowns (message_id, channel_id, creator_id, title, ends_at, \
+             next_update_at) VALUES ($1, $2, $3, $4, to_timestamp($5::BIGINT), \
+             to_timestamp($6::BIGINT))",
+            countdown.message_id.encode(),
+            countdown.channel_id.encode(),
+            countdown.creator_id.encode(),
+            countdown.title,
+            countdown.ends_at,
+            next_update_at
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn due_countdowns(&self) -> Result<Vec<Countdown>> {
+        Ok(query!(
+            r#"
+            SELECT message_id, channel_id, creator_id, title,
+                extract(epoch FROM ends_at)::BIGINT AS "ends_at!", is_final_edited
+            FROM countdowns
+            WHERE next_update_at <= now()"#
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|countdown| Countdown {
+            message_id: countdown.message_id.decode(),
+            channel_id: countdown.channel_id.decode(),
+            creator_id: countdown.creator_id.decode(),
+            title: countdown.title,
+            ends_at: countdown.ends_at,
+            is_final_edited: countdown.is_final_edited,
+        })
+        .collect())
+    }
+
+    pub async fn set_countdown_next_update(
+        &self,
+        message_id: Id<MessageMarker>,
+        next_update_at: i64,
+    ) -> Result<()> {
+        query!(
+            "UPDATE countdowns SET next_update_at = to_timestamp($2::BIGINT) WHERE message_id = $1",
+            message_id.encode(),
+            next_update_at
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_countdown_final_edited(&self, message_id: Id<MessageMarker>) -> Result<()> {
+        query!(
+            "UPDATE countdowns SET is_final_edited = TRUE WHERE message_id = $1",
+            message_id.encode()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete_countdown(&self, message_id: Id<MessageMarker>) -> Result<()> {
+        query!(
+            "DELETE FROM countdowns WHERE message_id = $1",
+            message_id.encode()
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_clock(&self, clock: &Clock) -> Result<()> {
+        query!(
+            "INSERT INTO clocks (guild_id, style, timezones, channel_ids, message_id) VALUES ($1, \
+             $2, $3, $4, $5) ON CONFLICT (guild_id) DO UPDATE SET style = $2, timezones = $3, \
+             channel_ids = $4, message_id = $5",
+            clock.guild_id.encode(),
+            clock.style as _,
+            &clock
+                .timezones
+                .iter()
+                .map(|tz| tz.encode().to_owned())
+                .collect::<Vec<String>>(),
+            &clock
+                .channel_ids
+                .iter()
+                .map(Encode::encode)
+                .collect::<Vec<i64>>(),
+            clock.message_id.as_ref().map(Encode::encode)
+        )
+        .execute(&self.db)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn clock(&self, guild_id: Id<GuildMarker>) -> Result<Option<Clock>> {
+        query!(
+            r#"
+            SELECT guild_id, style AS "style: ClockStyle", timezones, channel_ids, message_id
+            FROM clocks
+            WHERE guild_id = $1"#,
+            guild_id.encode()
+        )
+        .fetch_optional(&self.db)
+        .await?
+        .map(|clock| {
+            Ok(Clock {
+                guild_id: clock.guild_id.decode(),
+                style: clock.style,
+                timezones: clock
+                    .timezones
+                    .iter()
+                    .map(Decode::decode)
+                    .collect::<Result<_>>()?,
+                channel_ids: clock.channel_ids.iter().map(Decode::decode).collect(),
+                message_id: clock.message_id.as_ref().map(Decode::decode),
+            })
+        })
+        .transpose()
+    }
+
+    pub async fn clocks(&self, style: ClockStyle) -> Result<Vec<Clock>> {
+        query!(
+            r#"
+            SELECT guild_id, style AS "style: ClockStyle", timezones, channel_ids, message_id
+            FROM clocks
+            WHERE style = $1"#,
+            style as _
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|clock| {
+            Ok(Clock {
+                guild_id: clock.guild_id.decode(),
+                style: clock.style,
+                timezones: clock
+                    .timezones
+                    .iter()
+                    .map(Decode::decode)
+                    .collect::<Result<_>>()?,
+                channel_ids: clock.channel_ids.iter().map(Decode::decode).collect(),
+                message_id: clock.message_id.as_ref().map(Decode::decode),
+            })
+        })
+        .collect()
+    }
+
+    pub async fn delete_clock(&self, guild_id: Id<GuildMarker>) -> Result<()> {
+        query!("DELETE FROM clocks WHERE guild_id = $1", guild_id.encode())
+            .execute(&self.db)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn insert_guild_count(&self, count: i32) -> Result<()> {
         query!("INSERT INTO guild_count (count) VALUES ($1)", count)
             .execute(&self.db)
@@ -0,0 +1,115 @@
+use chrono::{DateTime, Utc};
+
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const MAX_LINE_LEN: usize = 75;
+
+#[derive(Clone, Debug)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub url: String,
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+pub fn calendar(events: &[CalendarEvent]) -> String {
+    let stamp = Utc::now().format(DATE_TIME_FORMAT).to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_owned(),
+        "VERSION:2.0".to_owned(),
+        "PRODID:-//timezoner//timezoner//EN".to_owned(),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_owned());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format!("DTSTART:{}", event.start.format(DATE_TIME_FORMAT)));
+        if let Some(end) = event.end {
+            lines.push(format!("DTEND:{}", end.format(DATE_TIME_FORMAT)));
+        }
+        lines.push(format!("SUMMARY:{}", escape(&event.summary)));
+        lines.push(format!("URL:{}", event.url));
+        lines.push("END:VEVENT".to_owned());
+    }
+    lines.push("END:VCALENDAR".to_owned());
+
+    let mut calendar = lines
+        .iter()
+        .map(|line| fold(line))
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    calendar.push_str("\r\n");
+    calendar
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut line_len = 0;
+    for char in line.chars() {
+        if line_len + char.len_utf8() > MAX_LINE_LEN {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+        folded.push(char);
+        line_len += char.len_utf8();
+    }
+
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn fold_long_lines() {
+        assert_eq!(fold("SUMMARY:short"), "SUMMARY:short");
+
+        let line = "a".repeat(MAX_LINE_LEN * 2);
+        let folded = fold(&line);
+        let parts = folded.split("\r\n").collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+        assert!(parts.iter().all(|part| part.len() <= MAX_LINE_LEN));
+        assert_eq!(folded.replace("\r\n ", ""), line);
+    }
+
+    #[test]
+    fn fold_keeps_chars_whole() {
+        let line = format!("{}🎉🎉", "a".repeat(MAX_LINE_LEN - 2));
+        let folded = fold(&line);
+        assert_eq!(folded, format!("{}\r\n 🎉🎉", "a".repeat(MAX_LINE_LEN - 2)));
+    }
+
+    #[test]
+    fn calendar_events() {
+        let calendar = calendar(&[CalendarEvent {
+            uid: "1@timezoner".to_owned(),
+            summary: "lunch, then; more".to_owned(),
+            url: "https://discord.com/channels/@me/1/2".to_owned(),
+            start: Utc.timestamp_opt(1_681_387_200, 0).unwrap(),
+            end: Some(Utc.timestamp_opt(1_681_390_800, 0).unwrap()),
+        }]);
+        let lines = calendar.split("\r\n").collect::<Vec<_>>();
+
+        assert_eq!(
+            lines.iter().filter(|line| **line == "BEGIN:VEVENT").count(),
+            1
+        );
+        assert!(lines.contains(&"DTSTART:20230413T120000Z"));
+        assert!(lines.contains(&"DTEND:20230413T130000Z"));
+        assert!(lines.contains(&"SUMMARY:lunch\\, then\\; more"));
+        assert_eq!(lines.first(), Some(&"BEGIN:VCALENDAR"));
+        assert_eq!(lines[lines.len() - 2], "END:VCALENDAR");
+        assert_eq!(lines.last(), Some(&""));
+    }
+}
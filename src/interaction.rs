@@ -6,16 +6,34 @@ use sparkle_convenience::{
 };
 use twilight_interactions::command::CreateCommand;
 use twilight_model::{
-    application::{command::Command, interaction::Interaction},
+    application::{
+        command::Command,
+        interaction::{Interaction, InteractionType},
+    },
     id::{marker::CommandMarker, Id},
 };
 
-use crate::{err_reply, Context, CustomError, Error, TEST_GUILD_ID};
+use crate::{database::RsvpStatus, err_reply, Context, CustomError, Error, TEST_GUILD_ID};
 
+mod available;
+pub mod calendar;
+mod clocks;
+mod convert;
+pub mod converted_message;
 mod copy;
+mod countdown;
 mod current_time;
 pub mod date;
+mod event;
 pub mod help;
+mod location_time;
+mod meeting_planner;
+mod preferences;
+mod recurring;
+pub mod reminder;
+mod server_timezones;
+mod settings;
+mod team_time;
 mod timezone;
 
 #[derive(Clone, Copy, Debug)]
@@ -50,7 +68,13 @@ struct InteractionContext<'ctx> {
 }
 
 impl<'ctx> InteractionContext<'ctx> {
+    // every handler's future is inlined into this match
+    #[allow(clippy::large_stack_frames)]
     async fn handle(self) -> Result<()> {
+        if self.interaction.kind == InteractionType::ApplicationCommandAutocomplete {
+            return self.handle_timezone_autocomplete().await;
+        }
+
         match self.interaction.name().ok()? {
             timezone::Command::NAME => self.handle_timezone_command().await,
             timezone::PASTE_BUTTON_CUSTOM_ID => self.handle_timezone_paste_button_click().await,
@@ -59,8 +83,34 @@ impl<'ctx> InteractionContext<'ctx> {
             timezone::DETECT_REJECT_CUSTOM_ID => self.handle_timezone_detect_reject().await,
             date::Command::NAME => self.handle_date_command().await,
             copy::NAME => self.handle_copy_command().await,
+            copy::SEND_SELECT_CUSTOM_ID => self.handle_copy_send_select().await,
+            converted_message::UNDO_BUTTON_CUSTOM_ID => self.handle_undo_button_click().await,
+            converted_message::EDIT_NAME => self.handle_edit_command().await,
+            converted_message::EDIT_MODAL_SUBMIT_ID => self.handle_edit_modal_submit().await,
+            converted_message::DELETE_NAME => self.handle_delete_command().await,
             current_time::NAME => self.handle_current_time_command().await,
+            current_time::SHARE_BUTTON_CUSTOM_ID => {
+                self.handle_current_time_share_button_click().await
+            }
+            countdown::Command::NAME => self.handle_countdown_command().await,
+            clocks::Command::NAME => self.handle_clocks_command().await,
+            convert::Command::NAME => self.handle_convert_command().await,
+            location_time::Command::NAME => self.handle_location_time_command().await,
             help::Command::NAME => self.handle_help_command().await,
+            settings::Command::NAME => self.handle_settings_command().await,
+            preferences::Command::NAME => self.handle_preferences_command().await,
+            recurring::Command::NAME => self.handle_recurring_command().await,
+            event::Command::NAME => self.handle_event_command().await,
+            event::GOING_BUTTON_CUSTOM_ID => self.handle_event_rsvp(RsvpStatus::Going).await,
+            event::MAYBE_BUTTON_CUSTOM_ID => self.handle_event_rsvp(RsvpStatus::Maybe).await,
+            event::NOT_GOING_BUTTON_CUSTOM_ID => self.handle_event_rsvp(RsvpStatus::NotGoing).await,
+            event::ATTENDEE_TIMES_BUTTON_CUSTOM_ID => self.handle_event_attendee_times().await,
+            reminder::REMIND_BUTTON_CUSTOM_ID => self.handle_remind_button_click().await,
+            server_timezones::Command::NAME => self.handle_server_timezones_command().await,
+            team_time::Command::NAME => self.handle_team_time_command().await,
+            meeting_planner::Command::NAME => self.handle_meeting_planner_command().await,
+            available::Command::NAME => self.handle_available_command().await,
+            calendar::CALENDAR_BUTTON_CUSTOM_ID => self.handle_calendar_button_click().await,
             name => Err(Error::UnknownCommand(name.to_owned()).into()),
         }
     }
@@ -72,7 +122,21 @@ pub async fn set_commands(bot: &Bot) -> Result<CommandIds> {
         date::Command::create_command().into(),
         copy::command().into(),
         current_time::command(),
+        location_time::Command::create_command().into(),
+        convert::Command::create_command().into(),
+        clocks::Command::create_command().into(),
+        countdown::Command::create_command().into(),
+        converted_message::edit_command(),
+        converted_message::delete_command(),
         help::Command::create_command().into(),
+        settings::Command::create_command().into(),
+        preferences::Command::create_command().into(),
+        recurring::Command::create_command().into(),
+        event::Command::create_command().into(),
+        team_time::Command::create_command().into(),
+        server_timezones::Command::create_command().into(),
+        meeting_planner::Command::create_command().into(),
+        available::Command::create_command().into(),
     ];
 
     let commands_response = bot
@@ -0,0 +1,78 @@
+use anyhow::Result;
+use chrono::Utc;
+use sparkle_convenience::{
+    error::IntoError,
+    interaction::{extract::InteractionDataExt, DeferBehavior, DeferVisibility},
+    reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::{marker::RoleMarker, Id};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::{
+    database::UsageKind, embed, interaction::InteractionContext, truncated, EMBED_DESCRIPTION_LIMIT,
+};
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "available",
+    desc = "see who's in their working hours right now, so you know who to ping",
+    dm_permission = false
+)]
+pub struct Command {
+    #[command(desc = "only show the people with this role")]
+    pub role: Option<Id<RoleMarker>>,
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_available_command(self) -> Result<()> {
+        let guild_id = self.interaction.guild_id.ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        // paging through the members can take longer than discord waits for a response
+        self.handle
+            .defer_with_behavior(DeferVisibility::Ephemeral, DeferBehavior::Followup)
+            .await?;
+        let member_ids = self.ctx.member_ids(guild_id, options.role).await?;
+        let working_hours = self.ctx.working_hours_of(&member_ids).await?;
+
+        let now = Utc::now();
+        let mut working_lines = vec![];
+        let mut not_working_count = 0;
+        for (user_id, tz, working_hours) in &working_hours {
+            let local = now.with_timezone(tz);
+            if !working_hours.is_working(local) {
+                not_working_count += 1;
+                continue;
+            }
+            working_lines.push(format!("<@{user_id}> {}\n", local.format("%-I:%M %p")));
+        }
+        let mut working = truncated(&working_lines, EMBED_DESCRIPTION_LIMIT);
+        if working.is_empty() {
+            working.push_str("nobody's working right now :zzz:");
+        }
+
+        self.handle
+            .reply(
+                Reply::new().ephemeral().embed(
+                    embed()
+                        .title("🟢 working right now")
+                        .description(working)
+                        .field(EmbedFieldBuilder::new(
+                            "everyone else",
+                            format!(
+                                "{not_working_count} people are outside their working hours, {} \
+                                 haven't set their working hours with `/preferences`",
+                                member_ids.len() - working_hours.len()
+                            ),
+                        ))
+                        .build(),
+                ),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Available).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use chrono::{TimeZone, Utc};
+use sparkle_convenience::{error::IntoError, reply::Reply};
+use twilight_model::{
+    channel::message::{
+        component::{Button, ButtonStyle},
+        Component, ReactionType,
+    },
+    http::attachment::Attachment,
+};
+
+use crate::{
+    database::UsageKind,
+    ics::{self, CalendarEvent},
+    interaction::InteractionContext,
+    time, CustomError,
+};
+
+pub const CALENDAR_BUTTON_CUSTOM_ID: &str = "calendar_add";
+
+pub fn calendar_button() -> Component {
+    Component::Button(Button {
+        custom_id: Some(CALENDAR_BUTTON_CUSTOM_ID.to_owned()),
+        emoji: Some(ReactionType::Unicode {
+            name: "📆".to_owned(),
+        }),
+        label: Some("add to calendar".to_owned()),
+        disabled: false,
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_calendar_button_click(self) -> Result<()> {
+        let message = self.interaction.message.ok()?;
+        let url = format!(
+            "https://discord.com/channels/{}/{}/{}",
+            self.interaction
+                .guild_id
+                .map_or_else(|| "@me".to_owned(), |guild_id| guild_id.to_string()),
+            message.channel_id,
+            message.id
+        );
+
+        let events = if let Some(event) = self.ctx.event(message.id).await? {
+            vec![CalendarEvent {
+                uid: format!("{}@timezoner", message.id),
+                summary: event.title,
+                url,
+                start: Utc.timestamp_opt(event.starts_at, 0).single().ok()?,
+                end: event
+                    .ends_at
+                    .and_then(|ends_at| Utc.timestamp_opt(ends_at, 0).single()),
+            }]
+        } else {
+            let mut ranges = time::timestamp_ranges_from_text(&message.content);
+            ranges.sort_unstable();
+            ranges.dedup();
+            ranges
+                .into_iter()
+                .filter_map(|(start, end)| {
+                    Some(CalendarEvent {
+                        uid: format!("{start}-{}@timezoner", message.id),
+                        summary: "a time from discord".to_owned(),
+                        url: url.clone(),
+                        start: Utc.timestamp_opt(start, 0).single()?,
+                        end: end.and_then(|end| Utc.timestamp_opt(end, 0).single()),
+                    })
+                })
+                .collect()
+        };
+        if events.is_empty() {
+            return Err(CustomError::NoTimeInMessage.into());
+        }
+
+        self.handle
+            .reply(
+                Reply::new()
+                    .ephemeral()
+                    .content("📆 open this file to add it to your calendar")
+                    .attachment(Attachment::from_bytes(
+                        "timezoner.ics".to_owned(),
+                        ics::calendar(&events).into_bytes(),
+                        0,
+                    )),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Calendar).await?;
+        Ok(())
+    }
+}
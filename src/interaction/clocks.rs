@@ -0,0 +1,153 @@
+use anyhow::Result;
+use chrono::Utc;
+use chrono_tz::Tz;
+use sparkle_convenience::{
+    error::IntoError,
+    interaction::{extract::InteractionDataExt, DeferBehavior, DeferVisibility},
+    reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{channel::message::Embed, guild::Permissions};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::{
+    clock::{location_name, ClockStyle, MAX_CLOCKS},
+    database::{Clock, UsageKind},
+    embed,
+    interaction::InteractionContext,
+    time, CustomError,
+};
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "clocks",
+    desc = "keep clocks for some places always visible in this server",
+    default_permissions = "default_permissions",
+    dm_permission = false
+)]
+pub struct Command {
+    #[command(
+        desc = "the places to show separated by commas, like tokyo, Europe/Berlin, UTC+3",
+        max_length = 500
+    )]
+    pub places: Option<String>,
+    #[command(desc = "how to show the clocks, a pinned message in this channel by default")]
+    pub style: Option<ClockStyle>,
+    #[command(desc = "stop the clocks and clean up after them")]
+    pub remove: Option<bool>,
+}
+
+const fn default_permissions() -> Permissions {
+    Permissions::MANAGE_GUILD
+}
+
+fn clock_settings_embed(clock: Option<&Clock>) -> Embed {
+    let Some(clock) = clock else {
+        return embed()
+            .title("🕒 no clocks here yet")
+            .description("give me some places to start the clocks")
+            .build();
+    };
+
+    embed()
+        .title("🕒 here are the clocks in this server")
+        .field(EmbedFieldBuilder::new(
+            "places",
+            clock
+                .timezones
+                .iter()
+                .map(|tz| location_name(*tz))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ))
+        .field(EmbedFieldBuilder::new("style", clock.style.description()))
+        .build()
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_clocks_command(self) -> Result<()> {
+        let guild_id = self.interaction.guild_id.ok()?;
+        let channel_id = self.interaction.channel_id.ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        let existing_clock = self.ctx.clock(guild_id).await?;
+
+        if options.remove == Some(true) {
+            if let Some(clock) = &existing_clock {
+                self.ctx.remove_clock(clock).await?;
+            }
+            self.handle
+                .reply(
+                    Reply::new()
+                        .ephemeral()
+                        .embed(embed().title("🕒 the clocks are gone").build()),
+                )
+                .await?;
+
+            self.ctx.insert_usage(UsageKind::Clocks).await?;
+            return Ok(());
+        }
+
+        let Some(places) = options.places else {
+            self.handle
+                .reply(
+                    Reply::new()
+                        .ephemeral()
+                        .embed(clock_settings_embed(existing_clock.as_ref())),
+                )
+                .await?;
+
+            self.ctx.insert_usage(UsageKind::Clocks).await?;
+            return Ok(());
+        };
+
+        let now = Utc::now();
+        let timezones = places
+            .split(',')
+            .map(str::trim)
+            .filter(|place| !place.is_empty())
+            .map(|place| time::tz_from_location(place, now).ok_or(CustomError::BadLocation))
+            .collect::<Result<Vec<Tz>, _>>()?;
+        if timezones.is_empty() {
+            return Err(CustomError::BadLocation.into());
+        }
+        if timezones.len() > MAX_CLOCKS {
+            return Err(CustomError::TooManyClocks.into());
+        }
+
+        // making up to 10 channels can take longer than discord waits for a response
+        self.handle
+            .defer_with_behavior(DeferVisibility::Ephemeral, DeferBehavior::Followup)
+            .await?;
+
+        // the old clocks stay until the new ones are up
+        let clock = self
+            .ctx
+            .create_clock(
+                guild_id,
+                channel_id,
+                options.style.unwrap_or(ClockStyle::PinnedMessage),
+                timezones,
+            )
+            .await?;
+        if let Err(err) = self.ctx.set_clock(&clock).await {
+            self.ctx.remove_clock_display(&clock).await?;
+            return Err(err);
+        }
+        if let Some(existing_clock) = &existing_clock {
+            self.ctx.remove_clock_display(existing_clock).await?;
+        }
+
+        self.handle
+            .reply(
+                Reply::new()
+                    .ephemeral()
+                    .embed(clock_settings_embed(Some(&clock))),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Clocks).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use sparkle_convenience::{
+    error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_util::builder::embed::EmbedFooterBuilder;
+
+use crate::{database::UsageKind, embed, interaction::InteractionContext, time, CustomError};
+
+const LOCAL_TIME_FORMAT: &str = "%A %-I:%M %p";
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "convert", desc = "convert a time from one place to another")]
+pub struct Command {
+    #[command(
+        desc = "the time to convert, like 9am, tomorrow 17:30 or friday noon",
+        max_length = 100
+    )]
+    pub when: String,
+    #[command(
+        desc = "the city, timezone or offset it's in, yours by default",
+        autocomplete = true
+    )]
+    pub from: Option<String>,
+    #[command(
+        desc = "the city, timezone or offset to convert to, everyone's own by default",
+        autocomplete = true
+    )]
+    pub to: Option<String>,
+}
+
+fn day_change_marker(from: DateTime<Tz>, to: DateTime<Tz>) -> String {
+    match (to.date_naive() - from.date_naive()).num_days() {
+        0 => "same day".to_owned(),
+        1 => "next day".to_owned(),
+        -1 => "previous day".to_owned(),
+        days => format!("{days:+} days"),
+    }
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_convert_command(self) -> Result<()> {
+        let author_id = self.interaction.author_id().ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        let now = Utc::now();
+        let from_tz = if let Some(from) = &options.from {
+            time::tz_from_location(from, now).ok_or(CustomError::BadLocation)?
+        } else {
+            self.ctx
+                .timezone(author_id)
+                .await?
+                .ok_or(CustomError::MissingTimezone(self.ctx.command_ids.timezone))?
+        };
+        let to_tz = options
+            .to
+            .as_deref()
+            .map(|to| time::tz_from_location(to, now).ok_or(CustomError::BadLocation))
+            .transpose()?;
+
+        let local = time::parse_when(&options.when, now.with_timezone(&from_tz).naive_local())?
+            .ok_or(CustomError::BadWhen)?;
+        let (from_time, note) =
+            time::resolve_local(from_tz, local, self.ctx.user_dst_policy(author_id).await?);
+
+        let description = to_tz.map_or_else(
+            || {
+                let timestamp = from_time.timestamp();
+                format!(
+                    "**{}** in {} is <t:{timestamp}:F> for everyone (<t:{timestamp}:R>)\n\
+                     copy it: `<t:{timestamp}:F>`",
+                    from_time.format(LOCAL_TIME_FORMAT),
+                    from_tz.name(),
+                )
+            },
+            |to_tz| {
+                let to_time = from_time.with_timezone(&to_tz);
+                format!(
+                    "**{}** in {} is\n**{}** in {} ({})",
+                    from_time.format(LOCAL_TIME_FORMAT),
+                    from_tz.name(),
+                    to_time.format(LOCAL_TIME_FORMAT),
+                    to_tz.name(),
+                    day_change_marker(from_time, to_time)
+                )
+            },
+        );
+
+        let mut embed = embed().title("🔁 here you go").description(description);
+        if let Some(note) = note {
+            embed = embed.footer(EmbedFooterBuilder::new(note.description()));
+        }
+
+        self.handle
+            .reply(Reply::new().ephemeral().embed(embed.build()))
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Convert).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use anyhow::Result;
+use sparkle_convenience::{
+    error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
+};
+use twilight_model::{
+    application::command::{Command, CommandType},
+    channel::message::{
+        component::{Button, ButtonStyle, TextInput, TextInputStyle},
+        AllowedMentions, Component, ReactionType,
+    },
+    id::{
+        marker::{ChannelMarker, MessageMarker, UserMarker},
+        Id,
+    },
+};
+use twilight_util::builder::{command::CommandBuilder, embed::EmbedFieldBuilder};
+
+use crate::{
+    database::{ConvertedMessage, UsageKind},
+    embed,
+    interaction::InteractionContext,
+    message::{avatar_url, notes_description},
+    time::{self, ParsedTime},
+    Context, CustomError,
+};
+
+pub const EDIT_NAME: &str = "edit my converted message";
+pub const DELETE_NAME: &str = "delete my converted message";
+
+pub const UNDO_BUTTON_CUSTOM_ID: &str = "converted_message_undo";
+pub const EDIT_MODAL_SUBMIT_ID: &str = "converted_message_edit_modal_submit";
+
+pub fn edit_command() -> Command {
+    CommandBuilder::new(EDIT_NAME, "", CommandType::Message)
+        .dm_permission(false)
+        .build()
+}
+
+pub fn undo_button() -> Component {
+    Component::Button(Button {
+        custom_id: Some(UNDO_BUTTON_CUSTOM_ID.to_owned()),
+        emoji: Some(ReactionType::Unicode {
+            name: "↩️".to_owned(),
+        }),
+        label: Some("oops, undo".to_owned()),
+        disabled: false,
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
+
+pub fn delete_command() -> Command {
+    CommandBuilder::new(DELETE_NAME, "", CommandType::Message)
+        .dm_permission(false)
+        .build()
+}
+
+impl Context {
+    pub async fn remove_converted_message(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        message_id: Id<MessageMarker>,
+    ) -> Result<()> {
+        // removing the row first also cancels the pending undo expiry
+        self.delete_converted_message(message_id).await?;
+        self.bot.http.delete_message(channel_id, message_id).await?;
+
+        Ok(())
+    }
+
+    async fn check_converted_message_author(
+        &self,
+        message_id: Id<MessageMarker>,
+        user_id: Id<UserMarker>,
+    ) -> Result<ConvertedMessage> {
+        let converted_message = self
+            .converted_message(message_id)
+            .await?
+            .ok_or(CustomError::NotConvertedMessage)?;
+        if converted_message.author_id != user_id {
+            return Err(CustomError::NotConvertedMessageAuthor.into());
+        }
+
+        Ok(converted_message)
+    }
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_undo_button_click(self) -> Result<()> {
+        let user_id = self.interaction.author_id().ok()?;
+        let message = self.interaction.message.ok()?;
+
+        let converted_message = self
+            .ctx
+            .converted_message(message.id)
+            .await?
+            .ok_or(CustomError::UndoExpired)?;
+        if converted_message.author_id != user_id {
+            return Err(CustomError::NotConvertedMessageAuthor.into());
+        }
+        let original_content = converted_message
+            .original_content
+            .ok_or(CustomError::UndoExpired)?;
+
+        let (channel_id, thread_id) = self.ctx.webhook_channel(message.channel_id).await?;
+        let webhook = self.ctx.webhook_for_channel(channel_id).await?;
+
+        let allowed_mentions = AllowedMentions::default();
+        let avatar_url = avatar_url(
+            None,
+            message.author.avatar,
+            message.author.id,
+            None,
+            message.author.discriminator,
+        );
+        let mut execute_webhook = self
+            .ctx
+            .bot
+            .http
+            .execute_webhook(webhook.id, &webhook.token)
+            .content(&original_content)
+            .map_err(|_| CustomError::MessageTooLong)?
+            .allowed_mentions(Some(&allowed_mentions))
+            .username(&message.author.name)?
+            .avatar_url(&avatar_url);
+        let mut delete_webhook_message =
+            self.ctx
+                .bot
+                .http
+                .delete_webhook_message(webhook.id, &webhook.token, message.id);
+        if let Some(thread_id) = thread_id {
+            execute_webhook = execute_webhook.thread_id(thread_id);
+            delete_webhook_message = delete_webhook_message.thread_id(thread_id);
+        }
+
+        let restored_message = execute_webhook.wait().await?.model().await?;
+        delete_webhook_message.await?;
+        self.ctx.delete_converted_message(message.id).await?;
+        // so the author can still edit or delete it
+        self.ctx
+            .insert_converted_message(restored_message.id, user_id, None)
+            .await?;
+
+        self.handle
+            .reply(
+                Reply::new().ephemeral().embed(
+                    embed()
+                        .title("↩️ undone")
+                        .description("your message is back to how you wrote it")
+                        .build(),
+                ),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::TimeConvertUndo).await?;
+        Ok(())
+    }
+
+    pub async fn handle_edit_command(self) -> Result<()> {
+        let user_id = self.interaction.author_id().ok()?;
+        let message = self
+            .interaction
+            .data
+            .ok()?
+            .command()
+            .ok()?
+            .resolved
+            .ok()?
+            .messages
+            .into_values()
+            .next()
+            .ok()?;
+
+        let converted_message = self
+            .ctx
+            .check_converted_message_author(message.id, user_id)
+            .await?;
+        let content = match (
+            converted_message.original_content,
+            self.ctx.timezone(user_id).await?,
+        ) {
+            (Some(original_content), _) => original_content,
+            (None, Some(tz)) => time::plain_times_from_text(&message.content, tz),
+            (None, None) => message.content,
+        };
+
+        self.handle
+            .modal(
+                EDIT_MODAL_SUBMIT_ID.to_owned(),
+                "time for some edits".to_owned(),
+                vec![TextInput {
+                    // modals don't carry the message they're about, so the id is passed here
+                    custom_id: message.id.to_string(),
+                    style: TextInputStyle::Paragraph,
+                    label: "your message".to_owned(),
+                    placeholder: None,
+                    required: Some(true),
+                    max_length: Some(2000),
+                    min_length: None,
+                    value: Some(content),
+                }],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn handle_edit_modal_submit(self) -> Result<()> {
+        let user_id = self.interaction.author_id().ok()?;
+        let channel_id = self.interaction.channel_id.ok()?;
+        let text_input = self
+            .interaction
+            .data
+            .ok()?
+            .modal()
+            .ok()?
+            .components
+            .into_iter()
+            .next()
+            .ok()?
+            .components
+            .into_iter()
+            .next()
+            .ok()?;
+        let message_id = text_input.custom_id.parse()?;
+        let content = text_input.value.ok()?;
+
+        self.ctx
+            .check_converted_message_author(message_id, user_id)
+            .await?;
+
+        let parsed_times = ParsedTime::all_from_text(&content)?;
+        let (content, notes) = self
+            .ctx
+            .convert_content(user_id, self.interaction.guild_id, &content, &parsed_times)
+            .await?;
+
+        let (channel_id, thread_id) = self.ctx.webhook_channel(channel_id).await?;
+        let webhook = self.ctx.webhook_for_channel(channel_id).await?;
+
+        let allowed_mentions = AllowedMentions::default();
+        let mut update_webhook_message = self
+            .ctx
+            .bot
+            .http
+            .update_webhook_message(webhook.id, &webhook.token, message_id)
+            .content(Some(&content))
+            .map_err(|_| CustomError::MessageTooLong)?
+            .allowed_mentions(Some(&allowed_mentions));
+        if let Some(thread_id) = thread_id {
+            update_webhook_message = update_webhook_message.thread_id(thread_id);
+        }
+        update_webhook_message.await?;
+
+        let mut embed = embed()
+            .title("✏️ edited")
+            .description("your message is updated and the times in it are converted");
+        if !notes.is_empty() {
+            embed = embed.field(EmbedFieldBuilder::new(
+                "⚠️ heads up",
+                notes_description(&notes),
+            ));
+        }
+        self.handle
+            .reply(Reply::new().ephemeral().embed(embed.build()))
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::TimeConvertEdit).await?;
+        Ok(())
+    }
+
+    pub async fn handle_delete_command(self) -> Result<()> {
+        let user_id = self.interaction.author_id().ok()?;
+        let message = self
+            .interaction
+            .data
+            .ok()?
+            .command()
+            .ok()?
+            .resolved
+            .ok()?
+            .messages
+            .into_values()
+            .next()
+            .ok()?;
+
+        self.ctx
+            .check_converted_message_author(message.id, user_id)
+            .await?;
+
+        self.ctx
+            .remove_converted_message(message.channel_id, message.id)
+            .await?;
+
+        self.handle
+            .reply(
+                Reply::new().ephemeral().embed(
+                    embed()
+                        .title("🗑️ deleted")
+                        .description("your message is gone, like it was never there")
+                        .build(),
+                ),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::TimeConvertDelete).await?;
+        Ok(())
+    }
+}
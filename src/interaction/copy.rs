@@ -3,20 +3,31 @@ use sparkle_convenience::{
     error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
 };
 use twilight_interactions::command::{ApplicationCommandData, CommandModel, CreateCommand};
+use twilight_model::channel::message::{
+    component::{ActionRow, SelectMenu, SelectMenuOption},
+    Component,
+};
+use twilight_util::builder::embed::{EmbedFieldBuilder, EmbedFooterBuilder};
 
 use crate::{
     database::UsageKind,
-    interaction::{date, InteractionContext},
-    time,
+    embed,
+    interaction::{
+        date::{self, Style},
+        InteractionContext,
+    },
+    message::notes_description,
 };
 
 pub const NAME: &str = "copy";
 
+pub const SEND_SELECT_CUSTOM_ID: &str = "copy_send_select";
+
 pub fn command() -> ApplicationCommandData {
     let mut command = date::Command::create_command();
     command.name = NAME.to_owned();
     command.description =
-        "send a date that you can copy on desktop (on mobile just copy the message)".to_owned();
+        "get a date in every style to copy, on mobile just long press the one you want".to_owned();
     command
 }
 
@@ -26,16 +37,70 @@ impl InteractionContext<'_> {
         let options =
             date::Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
 
-        let time = self.ctx.user_time(author_id, options).await?;
+        let (time, end, notes) = self.ctx.user_range(author_id, &options).await?;
+
+        let mut embed = embed()
+            .title("📋 here it is in every style")
+            .description(format!(
+                "copy the one you like, or pick it below to send it here{}",
+                options.timezone_note(time)
+            ));
+        if !notes.is_empty() {
+            embed = embed.footer(EmbedFooterBuilder::new(notes_description(&notes)));
+        }
+        let mut select_options = vec![];
+        for style in Style::ALL {
+            let formatted = options.format_with_style(time, end, Some(style));
+            embed = embed.field(EmbedFieldBuilder::new(
+                style.name(),
+                format!("{formatted}\n`{formatted}`"),
+            ));
+            select_options.push(SelectMenuOption {
+                default: false,
+                description: None,
+                emoji: None,
+                label: style.name().to_owned(),
+                value: formatted,
+            });
+        }
+
         self.handle
             .reply(
                 Reply::new()
                     .ephemeral()
-                    .content(&(format!("`{}`", time::format(time, options.style)))),
+                    .embed(embed.build())
+                    .component(Component::ActionRow(ActionRow {
+                        components: vec![Component::SelectMenu(SelectMenu {
+                            custom_id: SEND_SELECT_CUSTOM_ID.to_owned(),
+                            disabled: false,
+                            max_values: None,
+                            min_values: None,
+                            options: select_options,
+                            placeholder: Some("📨 send one here for everyone".to_owned()),
+                        })],
+                    })),
             )
             .await?;
 
         self.ctx.insert_usage(UsageKind::Copy).await?;
         Ok(())
     }
+
+    pub async fn handle_copy_send_select(self) -> Result<()> {
+        let formatted = self
+            .interaction
+            .data
+            .ok()?
+            .component()
+            .ok()?
+            .values
+            .into_iter()
+            .next()
+            .ok()?;
+
+        self.handle.reply(Reply::new().content(formatted)).await?;
+
+        self.ctx.insert_usage(UsageKind::CopySend).await?;
+        Ok(())
+    }
 }
@@ -0,0 +1,105 @@
+use anyhow::Result;
+use chrono::Utc;
+use sparkle_convenience::{
+    error::{ErrorExt, IntoError},
+    interaction::extract::InteractionDataExt,
+    reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::guild::Permissions;
+
+use crate::{
+    countdown::{countdown_embed, next_update},
+    database::{Countdown, UsageKind},
+    embed,
+    interaction::{date, InteractionContext},
+    CustomError,
+};
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "countdown",
+    desc = "count down to something in a message that keeps itself up to date",
+    dm_permission = false
+)]
+pub struct Command {
+    #[command(desc = "what you're counting down to", max_length = 100)]
+    pub title: String,
+    #[command(
+        desc = "when it happens, like friday 8pm or 2026-12-31 23:59",
+        max_length = 100
+    )]
+    pub when: String,
+    #[command(
+        desc = "the timezone to use for the time instead of yours",
+        autocomplete = true
+    )]
+    pub timezone: Option<String>,
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_countdown_command(self) -> Result<()> {
+        let author_id = self.interaction.author_id().ok()?;
+        let channel_id = self.interaction.channel_id.ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        let ends_at = self
+            .ctx
+            .user_time(
+                author_id,
+                &date::Command {
+                    when: Some(options.when),
+                    timezone: options.timezone,
+                    ..date::Command::default()
+                },
+            )
+            .await?
+            .0
+            .timestamp();
+        let now = Utc::now().timestamp();
+        if ends_at <= now {
+            return Err(CustomError::CountdownInPast.into());
+        }
+
+        // the message isn't an interaction response so that i can keep editing it
+        let message = async {
+            Ok::<_, anyhow::Error>(
+                self.ctx
+                    .bot
+                    .http
+                    .create_message(channel_id)
+                    .embeds(&[countdown_embed(&options.title, ends_at, now)])?
+                    .await?
+                    .model()
+                    .await?,
+            )
+        }
+        .await
+        .map_err(|err| {
+            err.with_permissions(Permissions::SEND_MESSAGES | Permissions::EMBED_LINKS)
+        })?;
+        let countdown = Countdown {
+            message_id: message.id,
+            channel_id,
+            creator_id: author_id,
+            title: options.title,
+            ends_at,
+            is_final_edited: false,
+        };
+        self.ctx
+            .insert_countdown(&countdown, next_update(ends_at, now))
+            .await?;
+
+        self.handle
+            .reply(
+                Reply::new()
+                    .ephemeral()
+                    .embed(embed().title("⏳ the countdown is on").build()),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Countdown).await?;
+        Ok(())
+    }
+}
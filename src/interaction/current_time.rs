@@ -1,11 +1,19 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use sparkle_convenience::{
     error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
 };
-use twilight_model::application::command::{Command, CommandType};
+use twilight_model::{
+    application::command::{Command, CommandType},
+    channel::message::{
+        component::{ActionRow, Button, ButtonStyle},
+        Component, ReactionType,
+    },
+};
 use twilight_util::builder::{
     command::CommandBuilder,
-    embed::{EmbedAuthorBuilder, ImageSource},
+    embed::{EmbedAuthorBuilder, EmbedBuilder, EmbedFieldBuilder, ImageSource},
 };
 
 use crate::{
@@ -13,60 +21,148 @@ use crate::{
     embed,
     interaction::{date, InteractionContext},
     message::avatar_url,
-    CustomError,
+    time, CustomError,
 };
 
 pub const NAME: &str = "get current time for user";
 
+pub const SHARE_BUTTON_CUSTOM_ID: &str = "current_time_share";
+
+const LOCAL_TIME_FORMAT: &str = "%A, %B %-d, %-Y %-I:%M %p";
+const COMPARISON_FIELD_NAME: &str = "compared to you";
+
 pub fn command() -> Command {
     CommandBuilder::new(NAME, "", CommandType::User)
         .dm_permission(false)
         .build()
 }
 
+pub fn time_card(tz: Tz, now: DateTime<Utc>) -> EmbedBuilder {
+    embed()
+        .description(now.with_timezone(&tz).format(LOCAL_TIME_FORMAT).to_string())
+        .field(EmbedFieldBuilder::new("timezone", tz.name()).inline())
+        .field(
+            EmbedFieldBuilder::new("utc offset", time::format_offset(time::utc_offset(tz, now)))
+                .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new(
+                "daylight saving",
+                if time::is_dst(tz, now) { "on" } else { "off" },
+            )
+            .inline(),
+        )
+        .field(EmbedFieldBuilder::new(
+            "next clock change",
+            time::next_offset_change(tz, now).map_or_else(
+                || "not in the next year".to_owned(),
+                |change| format!("<t:{0}:F> (<t:{0}:R>)", change.timestamp()),
+            ),
+        ))
+}
+
+pub fn comparison_field(tz: Tz, author_tz: Tz, now: DateTime<Utc>) -> EmbedFieldBuilder {
+    EmbedFieldBuilder::new(
+        COMPARISON_FIELD_NAME,
+        time::format_offset_difference(
+            time::utc_offset(tz, now) - time::utc_offset(author_tz, now),
+        ),
+    )
+}
+
+pub fn share_button() -> Component {
+    Component::Button(Button {
+        custom_id: Some(SHARE_BUTTON_CUSTOM_ID.to_owned()),
+        emoji: Some(ReactionType::Unicode {
+            name: "📢".to_owned(),
+        }),
+        label: Some("show everyone".to_owned()),
+        disabled: false,
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
+
 impl InteractionContext<'_> {
     pub async fn handle_current_time_command(self) -> Result<()> {
+        let author_id = self.interaction.author_id().ok()?;
         let resolved = self.interaction.data.ok()?.command().ok()?.resolved.ok()?;
         let member = resolved.members.into_iter().next().ok()?.1;
         let user = resolved.users.into_iter().next().ok()?.1;
 
+        let (time, _) = self
+            .ctx
+            .user_time(user.id, &date::Command::default())
+            .await
+            .map_err(|err| {
+                if let Some(CustomError::MissingTimezone(command_id)) = err.downcast_ref() {
+                    CustomError::OtherUserMissingTimezone(*command_id).into()
+                } else {
+                    err
+                }
+            })?;
+        let tz = time.timezone();
+        let now = time.with_timezone(&Utc);
+
+        let mut description = time.format(LOCAL_TIME_FORMAT).to_string();
+        if let Some(working_hours) = self.ctx.working_hours(user.id).await? {
+            description.push_str(if working_hours.is_working(time) {
+                " — inside their working hours"
+            } else {
+                " — outside their working hours, maybe don't ping them"
+            });
+        }
+
+        let mut embed = time_card(tz, now)
+            .author(
+                EmbedAuthorBuilder::new(member.nick.unwrap_or(user.name)).icon_url(
+                    ImageSource::url(avatar_url(
+                        member.avatar,
+                        user.avatar,
+                        user.id,
+                        self.interaction.guild_id,
+                        user.discriminator,
+                    ))?,
+                ),
+            )
+            .description(description);
+        if author_id != user.id {
+            if let Some(author_tz) = self.ctx.timezone(author_id).await? {
+                embed = embed.field(comparison_field(tz, author_tz, now));
+            }
+        }
+
         self.handle
             .reply(
-                Reply::new().ephemeral().embed(
-                    embed()
-                        .author(
-                            EmbedAuthorBuilder::new(member.nick.unwrap_or(user.name)).icon_url(
-                                ImageSource::url(avatar_url(
-                                    member.avatar,
-                                    user.avatar,
-                                    user.id,
-                                    self.interaction.guild_id,
-                                    user.discriminator,
-                                ))?,
-                            ),
-                        )
-                        .description(
-                            self.ctx
-                                .user_time(user.id, date::Command::default())
-                                .await
-                                .map_err(|err| {
-                                    if let Some(CustomError::MissingTimezone(command_id)) =
-                                        err.downcast_ref()
-                                    {
-                                        CustomError::OtherUserMissingTimezone(*command_id).into()
-                                    } else {
-                                        err
-                                    }
-                                })?
-                                .format("%A, %B %-d, %-Y %-I:%M %p")
-                                .to_string(),
-                        )
-                        .build(),
-                ),
+                Reply::new()
+                    .ephemeral()
+                    .embed(embed.build())
+                    .component(Component::ActionRow(ActionRow {
+                        components: vec![share_button()],
+                    })),
             )
             .await?;
 
         self.ctx.insert_usage(UsageKind::CurrentTime).await?;
         Ok(())
     }
+
+    pub async fn handle_current_time_share_button_click(self) -> Result<()> {
+        let mut embeds = self.interaction.message.ok()?.embeds;
+        for embed in &mut embeds {
+            // comparisons are relative to whoever asked, so they'd be wrong for everyone else
+            embed
+                .fields
+                .retain(|field| field.name != COMPARISON_FIELD_NAME);
+        }
+
+        let mut reply = Reply::new();
+        for embed in embeds {
+            reply = reply.embed(embed);
+        }
+        self.handle.reply(reply).await?;
+
+        self.ctx.insert_usage(UsageKind::CurrentTimeShare).await?;
+        Ok(())
+    }
 }
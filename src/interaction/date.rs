@@ -1,10 +1,23 @@
 use anyhow::Result;
+use chrono::DateTime;
+use chrono_tz::Tz;
 use sparkle_convenience::{
     error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
 };
 use twilight_interactions::command::{CommandModel, CommandOption, CreateCommand, CreateOption};
+use twilight_model::{
+    channel::message::{component::ActionRow, Component},
+    id::{marker::UserMarker, Id},
+};
 
-use crate::{database::UsageKind, interaction::InteractionContext, time};
+use crate::{
+    database::UsageKind,
+    embed,
+    interaction::{calendar::calendar_button, reminder::remind_button, InteractionContext},
+    message::notes_description,
+    time::{self, DstPolicy, LocalTimeNote},
+    Context, CustomError,
+};
 
 #[derive(Clone, Copy, CommandOption, CreateOption)]
 pub enum Style {
@@ -24,6 +37,30 @@ pub enum Style {
     Relative,
 }
 
+impl Style {
+    pub const ALL: [Self; 7] = [
+        Self::ShortTime,
+        Self::LongTime,
+        Self::ShortDate,
+        Self::LongDate,
+        Self::ShortDateTime,
+        Self::LongDateTime,
+        Self::Relative,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::ShortTime => "short time",
+            Self::LongTime => "long time",
+            Self::ShortDate => "short date",
+            Self::LongDate => "long date",
+            Self::ShortDateTime => "short date and time",
+            Self::LongDateTime => "long date and time",
+            Self::Relative => "relative",
+        }
+    }
+}
+
 #[derive(Clone, Copy, CommandOption, CreateOption)]
 pub enum Month {
     #[option(name = "january", value = 1)]
@@ -52,12 +89,17 @@ pub enum Month {
     December,
 }
 
-#[derive(Clone, Copy, Default, CommandModel, CreateCommand)]
+#[derive(Clone, Default, CommandModel, CreateCommand)]
 #[command(
     name = "date",
     desc = "send a date that everyone sees in their own timezone"
 )]
 pub struct Command {
+    #[command(
+        desc = "when it is, like tomorrow 5pm or next friday noon, the other options change parts of it",
+        max_length = 100
+    )]
+    pub when: Option<String>,
     #[command(
         desc = "the day of the date, today by default",
         min_value = 0,
@@ -96,6 +138,76 @@ pub struct Command {
     pub second: Option<i64>,
     #[command(desc = "the style of the date, by default like Tuesday, 20 April 2021 4:20 PM")]
     pub style: Option<Style>,
+    #[command(
+        desc = "when it ends, like 7pm or in 2 hours, counted from the date",
+        max_length = 100
+    )]
+    pub end: Option<String>,
+    #[command(desc = "another style to also show the date in, like in an hour")]
+    pub extra_style: Option<Style>,
+    #[command(
+        desc = "the timezone the date is in, like Asia/Tokyo, yours by default",
+        autocomplete = true
+    )]
+    pub timezone: Option<String>,
+    #[command(desc = "someone whose timezone the date is in, yours by default")]
+    pub user: Option<Id<UserMarker>>,
+}
+
+impl Command {
+    pub fn end_time(
+        &self,
+        start: DateTime<Tz>,
+        policy: DstPolicy,
+    ) -> Result<(Option<DateTime<Tz>>, Option<LocalTimeNote>)> {
+        let Some(end) = &self.end else {
+            return Ok((None, None));
+        };
+
+        let end = time::parse_when(end, start.naive_local())?.ok_or(CustomError::BadWhen)?;
+        let (end, note) = time::resolve_local(start.timezone(), end, policy);
+        if end <= start {
+            return Err(CustomError::EndBeforeStart.into());
+        }
+
+        Ok((Some(end), note))
+    }
+
+    pub fn format(&self, start: DateTime<Tz>, end: Option<DateTime<Tz>>) -> String {
+        self.format_with_style(start, end, self.style)
+    }
+
+    pub fn format_with_style(
+        &self,
+        start: DateTime<Tz>,
+        end: Option<DateTime<Tz>>,
+        style: Option<Style>,
+    ) -> String {
+        time::format_range(start, end, style, self.extra_style)
+    }
+
+    pub fn timezone_note(&self, time: DateTime<Tz>) -> String {
+        if self.timezone.is_some() || self.user.is_some() {
+            format!(" _({} time)_", time.timezone().name())
+        } else {
+            String::new()
+        }
+    }
+}
+
+impl Context {
+    /// the start and end of the date, with notes on the times that fell in a
+    /// daylight saving change
+    pub async fn user_range(
+        &self,
+        user_id: Id<UserMarker>,
+        date: &Command,
+    ) -> Result<(DateTime<Tz>, Option<DateTime<Tz>>, Vec<LocalTimeNote>)> {
+        let (start, start_note) = self.user_time(user_id, date).await?;
+        let (end, end_note) = date.end_time(start, self.user_dst_policy(user_id).await?)?;
+
+        Ok((start, end, start_note.into_iter().chain(end_note).collect()))
+    }
 }
 
 impl InteractionContext<'_> {
@@ -104,10 +216,32 @@ impl InteractionContext<'_> {
         let options =
             Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
 
-        let time = self.ctx.user_time(author_id, options).await?;
+        let (time, end, notes) = self.ctx.user_range(author_id, &options).await?;
         self.handle
-            .reply(Reply::new().content(time::format(time, options.style)))
+            .reply(
+                Reply::new()
+                    .content(format!(
+                        "{}{}",
+                        options.format(time, end),
+                        options.timezone_note(time)
+                    ))
+                    .component(Component::ActionRow(ActionRow {
+                        components: vec![remind_button(), calendar_button()],
+                    })),
+            )
             .await?;
+        if !notes.is_empty() {
+            self.handle
+                .reply(
+                    Reply::new().ephemeral().embed(
+                        embed()
+                            .title("⚠️ heads up")
+                            .description(notes_description(&notes))
+                            .build(),
+                    ),
+                )
+                .await?;
+        }
 
         self.ctx.insert_usage(UsageKind::Date).await?;
         Ok(())
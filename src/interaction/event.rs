@@ -0,0 +1,253 @@
+use anyhow::Result;
+use chrono::{Duration, TimeZone, Utc};
+use sparkle_convenience::{
+    error::IntoError,
+    interaction::{extract::InteractionDataExt, DeferBehavior, DeferVisibility},
+    reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    channel::message::{
+        component::{ActionRow, Button, ButtonStyle},
+        Component, Embed, ReactionType,
+    },
+    id::{marker::UserMarker, Id},
+};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::{
+    database::{Event, Rsvp, RsvpStatus, UsageKind},
+    embed,
+    interaction::{
+        calendar::calendar_button,
+        date::{self, Style},
+        InteractionContext,
+    },
+    time, truncated, CustomError, EMBED_DESCRIPTION_LIMIT, EMBED_FIELD_VALUE_LIMIT,
+};
+
+pub const GOING_BUTTON_CUSTOM_ID: &str = "event_going";
+pub const MAYBE_BUTTON_CUSTOM_ID: &str = "event_maybe";
+pub const NOT_GOING_BUTTON_CUSTOM_ID: &str = "event_not_going";
+pub const ATTENDEE_TIMES_BUTTON_CUSTOM_ID: &str = "event_attendee_times";
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "event",
+    desc = "announce an event that everyone sees in their own timezone and can join"
+)]
+pub struct Command {
+    #[command(desc = "what the event is", max_length = 100)]
+    pub title: String,
+    #[command(desc = "when it starts, like friday 8pm", max_length = 100)]
+    pub when: String,
+    #[command(
+        desc = "how long it lasts in minutes",
+        min_value = 1,
+        max_value = 10080
+    )]
+    pub duration: Option<i64>,
+}
+
+fn button(custom_id: &str, emoji: &str, label: &str, style: ButtonStyle) -> Component {
+    Component::Button(Button {
+        custom_id: Some(custom_id.to_owned()),
+        emoji: Some(ReactionType::Unicode {
+            name: emoji.to_owned(),
+        }),
+        label: Some(label.to_owned()),
+        disabled: false,
+        style,
+        url: None,
+    })
+}
+
+fn event_components() -> Component {
+    Component::ActionRow(ActionRow {
+        components: vec![
+            button(GOING_BUTTON_CUSTOM_ID, "✅", "going", ButtonStyle::Success),
+            button(MAYBE_BUTTON_CUSTOM_ID, "🤔", "maybe", ButtonStyle::Primary),
+            button(
+                NOT_GOING_BUTTON_CUSTOM_ID,
+                "❌",
+                "not going",
+                ButtonStyle::Danger,
+            ),
+            button(
+                ATTENDEE_TIMES_BUTTON_CUSTOM_ID,
+                "🕒",
+                "when is it for everyone",
+                ButtonStyle::Secondary,
+            ),
+            calendar_button(),
+        ],
+    })
+}
+
+fn mentions(rsvps: &[Rsvp], status: RsvpStatus) -> (usize, String) {
+    let mentions = rsvps
+        .iter()
+        .filter(|rsvp| rsvp.status == status)
+        .map(|rsvp| format!("<@{}> ", rsvp.user_id))
+        .collect::<Vec<_>>();
+
+    let mut value = truncated(&mentions, EMBED_FIELD_VALUE_LIMIT);
+    if value.is_empty() {
+        value.push_str("nobody yet");
+    }
+
+    (mentions.len(), value)
+}
+
+fn event_embed(event: &Event, rsvps: &[Rsvp]) -> Result<Embed> {
+    let start = Utc.timestamp_opt(event.starts_at, 0).single().ok()?;
+    let end = event
+        .ends_at
+        .and_then(|ends_at| Utc.timestamp_opt(ends_at, 0).single());
+
+    let mut embed = embed()
+        .title(format!("📅 {}", event.title))
+        .description(format!(
+            "{}\nhosted by <@{}>",
+            time::format_range(
+                start.with_timezone(&chrono_tz::UTC),
+                end.map(|end| end.with_timezone(&chrono_tz::UTC)),
+                Some(Style::LongDateTime),
+                Some(Style::Relative),
+            ),
+            event.creator_id
+        ));
+    for (status, name) in [
+        (RsvpStatus::Going, "✅ going"),
+        (RsvpStatus::Maybe, "🤔 maybe"),
+        (RsvpStatus::NotGoing, "❌ not going"),
+    ] {
+        let (count, mentions) = mentions(rsvps, status);
+        embed = embed.field(EmbedFieldBuilder::new(format!("{name} ({count})"), mentions).inline());
+    }
+
+    Ok(embed.build())
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_event_command(self) -> Result<()> {
+        let author_id = self.interaction.author_id().ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        let (start, _) = self
+            .ctx
+            .user_time(
+                author_id,
+                &date::Command {
+                    when: Some(options.when),
+                    ..date::Command::default()
+                },
+            )
+            .await?;
+        let event = Event {
+            creator_id: author_id,
+            title: options.title,
+            starts_at: start.timestamp(),
+            ends_at: options
+                .duration
+                .map(|duration| (start + Duration::minutes(duration)).timestamp()),
+        };
+
+        self.handle
+            .defer_with_behavior(DeferVisibility::Visible, DeferBehavior::Update)
+            .await?;
+        let message = self
+            .handle
+            .reply(
+                Reply::new()
+                    .update_last()
+                    .embed(event_embed(&event, &[])?)
+                    .component(event_components()),
+            )
+            .await?
+            .ok()?;
+        self.ctx.insert_event(message.id, &event).await?;
+
+        self.ctx.insert_usage(UsageKind::Event).await?;
+        Ok(())
+    }
+
+    pub async fn handle_event_rsvp(self, status: RsvpStatus) -> Result<()> {
+        let user_id = self.interaction.author_id().ok()?;
+        let message_id = self.interaction.message.as_ref().ok()?.id;
+        let event = self
+            .ctx
+            .event(message_id)
+            .await?
+            .ok_or(CustomError::NotEvent)?;
+
+        self.ctx
+            .set_rsvp(message_id, Rsvp { user_id, status })
+            .await?;
+        let rsvps = self.ctx.rsvps(message_id).await?;
+
+        self.handle
+            .reply(
+                Reply::new()
+                    .update_last()
+                    .embed(event_embed(&event, &rsvps)?)
+                    .component(event_components()),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::EventRsvp).await?;
+        Ok(())
+    }
+
+    pub async fn handle_event_attendee_times(self) -> Result<()> {
+        let message_id = self.interaction.message.as_ref().ok()?.id;
+        let event = self
+            .ctx
+            .event(message_id)
+            .await?
+            .ok_or(CustomError::NotEvent)?;
+        let start = Utc.timestamp_opt(event.starts_at, 0).single().ok()?;
+
+        let attendee_ids = self
+            .ctx
+            .rsvps(message_id)
+            .await?
+            .into_iter()
+            .filter(|rsvp| rsvp.status != RsvpStatus::NotGoing)
+            .map(|rsvp| rsvp.user_id)
+            .collect::<Vec<Id<UserMarker>>>();
+        let timezones = self.ctx.timezones(&attendee_ids).await?;
+
+        let lines = attendee_ids
+            .iter()
+            .map(
+                |user_id| match timezones.iter().find(|(id, _)| id == user_id) {
+                    Some((_, tz)) => format!(
+                        "<@{user_id}>: {} _({})_\n",
+                        start.with_timezone(tz).format("%A %-I:%M %p"),
+                        tz.name()
+                    ),
+                    None => format!("<@{user_id}>: hasn't set their timezone\n"),
+                },
+            )
+            .collect::<Vec<_>>();
+        let mut description = truncated(&lines, EMBED_DESCRIPTION_LIMIT);
+        if description.is_empty() {
+            description.push_str("nobody's going yet, be the first!");
+        }
+
+        self.handle
+            .reply(
+                Reply::new().ephemeral().embed(
+                    embed()
+                        .title(format!("🕒 when {} is for everyone", event.title))
+                        .description(description)
+                        .build(),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use chrono::Utc;
+use sparkle_convenience::{
+    error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::channel::message::{component::ActionRow, Component};
+
+use crate::{
+    database::UsageKind,
+    interaction::{current_time, InteractionContext},
+    time, CustomError,
+};
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "time",
+    desc = "see what time it is in a city, timezone or offset"
+)]
+pub struct Command {
+    #[command(
+        desc = "a city, timezone or offset, like tokyo, Europe/Berlin or UTC+3",
+        autocomplete = true
+    )]
+    pub location: String,
+    #[command(desc = "another place to compare with", autocomplete = true)]
+    pub location_2: Option<String>,
+    #[command(desc = "another place to compare with", autocomplete = true)]
+    pub location_3: Option<String>,
+    #[command(desc = "another place to compare with", autocomplete = true)]
+    pub location_4: Option<String>,
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_location_time_command(self) -> Result<()> {
+        let author_id = self.interaction.author_id().ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        let now = Utc::now();
+        let author_tz = self.ctx.timezone(author_id).await?;
+
+        let mut reply = Reply::new().ephemeral();
+        for location in [
+            Some(options.location),
+            options.location_2,
+            options.location_3,
+            options.location_4,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let tz = time::tz_from_location(&location, now).ok_or(CustomError::BadLocation)?;
+
+            let mut embed = current_time::time_card(tz, now).title(format!("🕒 {location}"));
+            if let Some(author_tz) = author_tz {
+                embed = embed.field(current_time::comparison_field(tz, author_tz, now));
+            }
+            reply = reply.embed(embed.build());
+        }
+
+        self.handle
+            .reply(reply.component(Component::ActionRow(ActionRow {
+                components: vec![current_time::share_button()],
+            })))
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Time).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, DurationRound, Timelike, Utc};
+use chrono_tz::Tz;
+use lazy_regex::{lazy_regex, Lazy, Regex};
+use sparkle_convenience::{
+    error::IntoError,
+    interaction::{extract::InteractionDataExt, DeferBehavior, DeferVisibility},
+    reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::{
+    marker::{RoleMarker, UserMarker},
+    Id,
+};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::{
+    database::UsageKind, embed, interaction::InteractionContext, time::is_working_hour, CustomError,
+};
+
+const PLANNED_HOURS: i64 = 24;
+const MAX_HEATMAP_MEMBERS: usize = 10;
+
+static REGEX_USER_MENTION: Lazy<Regex> = lazy_regex!(r#"<@!?(\d+)>"#);
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "meeting-planner",
+    desc = "find when everyone is working in the next day",
+    dm_permission = false
+)]
+pub struct Command {
+    #[command(desc = "the people to meet with, like @alice @bob", max_length = 1000)]
+    pub users: Option<String>,
+    #[command(desc = "the role to meet with")]
+    pub role: Option<Id<RoleMarker>>,
+    #[command(
+        desc = "the hour working hours start at in 24 hour format, everyone's own or 9 by default",
+        min_value = 0,
+        max_value = 23
+    )]
+    pub start_hour: Option<i64>,
+    #[command(
+        desc = "the hour working hours end at in 24 hour format, everyone's own or 17 by default",
+        min_value = 0,
+        max_value = 23
+    )]
+    pub end_hour: Option<i64>,
+}
+
+const fn heat_emoji(available: usize, total: usize) -> &'static str {
+    if available == total {
+        "🟩"
+    } else if available * 2 >= total {
+        "🟨"
+    } else if available > 0 {
+        "🟧"
+    } else {
+        "🟥"
+    }
+}
+
+fn windows(slots: &[(DateTime<Utc>, Vec<bool>)]) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+    let mut windows: Vec<(DateTime<Utc>, DateTime<Utc>)> = vec![];
+    for (start, availability) in slots {
+        if !availability.iter().all(|is_available| *is_available) {
+            continue;
+        }
+        let end = *start + Duration::hours(1);
+        match windows.last_mut() {
+            Some((_, last_end)) if last_end == start => *last_end = end,
+            _ => windows.push((*start, end)),
+        }
+    }
+
+    windows
+}
+
+fn plan_description(slots: &[(DateTime<Utc>, Vec<bool>)]) -> Result<String> {
+    let mut description = String::new();
+    let windows = windows(slots);
+    if windows.is_empty() {
+        description.push_str("there's no time everyone is working in the next day :pensive:\n");
+    } else {
+        description.push_str("everyone is working:\n");
+        for (start, end) in &windows {
+            writeln!(
+                description,
+                "<t:{}:f> – <t:{}:t>",
+                start.timestamp(),
+                end.timestamp()
+            )?;
+        }
+    }
+
+    description.push('\n');
+    for (slot, availability) in slots {
+        let available = availability
+            .iter()
+            .filter(|is_available| **is_available)
+            .count();
+        write!(
+            description,
+            "<t:{}:t> {} ",
+            slot.timestamp(),
+            heat_emoji(available, availability.len())
+        )?;
+        if availability.len() <= MAX_HEATMAP_MEMBERS {
+            for is_available in availability {
+                description.push(if *is_available { '▓' } else { '░' });
+            }
+        }
+        writeln!(description, " {available}/{}", availability.len())?;
+    }
+
+    Ok(description)
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_meeting_planner_command(self) -> Result<()> {
+        let guild_id = self.interaction.guild_id.ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+        let start_hour = options.start_hour.map_or(Ok(9), TryInto::try_into)?;
+        let end_hour = options.end_hour.map_or(Ok(17), TryInto::try_into)?;
+
+        let mut member_ids = options.users.as_deref().map_or_else(
+            || Ok(vec![]),
+            |users| {
+                REGEX_USER_MENTION
+                    .captures_iter(users)
+                    .map(|captures| Ok(captures[1].parse()?))
+                    .collect::<Result<Vec<Id<UserMarker>>>>()
+            },
+        )?;
+        if options.role.is_some() {
+            // paging through the members can take longer than discord waits for a response
+            self.handle
+                .defer_with_behavior(DeferVisibility::Ephemeral, DeferBehavior::Followup)
+                .await?;
+            member_ids.extend(self.ctx.member_ids(guild_id, options.role).await?);
+        }
+        member_ids.sort_unstable();
+        member_ids.dedup();
+        if member_ids.is_empty() {
+            return Err(CustomError::NoMeetingMembers.into());
+        }
+
+        let timezones: Vec<(Id<UserMarker>, Tz)> = self.ctx.timezones(&member_ids).await?;
+        if timezones.is_empty() {
+            return Err(
+                CustomError::OtherUserMissingTimezone(self.ctx.command_ids.timezone).into(),
+            );
+        }
+        let saved_working_hours = if options.start_hour.is_none() && options.end_hour.is_none() {
+            self.ctx.working_hours_of(&member_ids).await?
+        } else {
+            vec![]
+        };
+        let missing_ids = member_ids
+            .iter()
+            .filter(|user_id| !timezones.iter().any(|(id, _)| id == *user_id))
+            .collect::<Vec<_>>();
+
+        let now = Utc::now().duration_trunc(Duration::hours(1))?;
+        let slots = (0..PLANNED_HOURS)
+            .map(|hour| {
+                let slot = now + Duration::hours(hour);
+                let availability = timezones
+                    .iter()
+                    .map(|(user_id, tz)| {
+                        let local = slot.with_timezone(tz);
+                        saved_working_hours
+                            .iter()
+                            .find(|(id, ..)| id == user_id)
+                            .map_or_else(
+                                || is_working_hour(local.hour(), start_hour, end_hour),
+                                |(.., working_hours)| working_hours.is_working(local),
+                            )
+                    })
+                    .collect::<Vec<_>>();
+                (slot, availability)
+            })
+            .collect::<Vec<_>>();
+
+        let mut embed = embed()
+            .title("🗓️ when everyone can meet")
+            .description(plan_description(&slots)?);
+        if !timezones.is_empty() && timezones.len() <= MAX_HEATMAP_MEMBERS {
+            let mut order = String::new();
+            for (user_id, tz) in &timezones {
+                writeln!(order, "<@{user_id}> {}", tz.name())?;
+            }
+            embed = embed.field(EmbedFieldBuilder::new("from left to right", order));
+        }
+        if !missing_ids.is_empty() {
+            let mut missing = String::new();
+            for user_id in missing_ids.iter().take(30) {
+                write!(missing, "<@{user_id}> ")?;
+            }
+            embed = embed.field(EmbedFieldBuilder::new(
+                "hasn't set their timezone, so they're left out",
+                missing,
+            ));
+        }
+
+        self.handle
+            .reply(Reply::new().ephemeral().embed(embed.build()))
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::MeetingPlanner).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::Result;
+use sparkle_convenience::{
+    error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::channel::message::Embed;
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::{
+    database::UsageKind,
+    embed,
+    interaction::InteractionContext,
+    time::{Birthday, DaysOff, DstPolicy, TimeResolution, WorkingHours},
+    CustomError,
+};
+
+#[derive(CommandModel, CreateCommand)]
+#[command(name = "preferences", desc = "change how i work for you")]
+pub struct Command {
+    #[command(
+        desc = "which day i should use when converting a time without a date in your messages"
+    )]
+    pub time_resolution: Option<TimeResolution>,
+    #[command(desc = "whether to go back to the time resolution the server uses")]
+    pub server_time_resolution: Option<bool>,
+    #[command(desc = "which time i should use when the clocks skip or repeat it")]
+    pub dst_policy: Option<DstPolicy>,
+    #[command(
+        desc = "the hour you start working at in 24 hour format",
+        min_value = 0,
+        max_value = 23
+    )]
+    pub work_start: Option<i64>,
+    #[command(
+        desc = "the hour you stop working at in 24 hour format",
+        min_value = 0,
+        max_value = 23
+    )]
+    pub work_end: Option<i64>,
+    #[command(desc = "the days you don't work")]
+    pub days_off: Option<DaysOff>,
+    #[command(desc = "the month you were born in", min_value = 1, max_value = 12)]
+    pub birthday_month: Option<i64>,
+    #[command(
+        desc = "the day of the month you were born on",
+        min_value = 1,
+        max_value = 31
+    )]
+    pub birthday_day: Option<i64>,
+    #[command(desc = "whether to forget your birthday")]
+    pub forget_birthday: Option<bool>,
+    #[command(desc = "whether i wish you a happy birthday in this server")]
+    pub birthday_here: Option<bool>,
+}
+
+fn preferences_embed(
+    time_resolution: Option<TimeResolution>,
+    dst_policy: DstPolicy,
+    working_hours: Option<WorkingHours>,
+    birthday: Option<Birthday>,
+    is_birthday_member: Option<bool>,
+) -> Embed {
+    embed()
+        .title("🎛️ here's how i work for you")
+        .field(EmbedFieldBuilder::new(
+            "time resolution",
+            time_resolution.map_or(
+                "whatever the server uses, the mods can change it with `/settings`",
+                TimeResolution::description,
+            ),
+        ))
+        .field(EmbedFieldBuilder::new(
+            "clock changes",
+            dst_policy.description(),
+        ))
+        .field(EmbedFieldBuilder::new(
+            "working hours",
+            working_hours.map_or_else(
+                || {
+                    "not set, set `work_start` and `work_end` so people know when to ping you"
+                        .to_owned()
+                },
+                WorkingHours::description,
+            ),
+        ))
+        .field(EmbedFieldBuilder::new(
+            "birthday",
+            birthday.map_or_else(
+                || {
+                    "not set, set `birthday_month` and `birthday_day`, then `birthday_here` in \
+                     the servers you want a happy birthday in"
+                        .to_owned()
+                },
+                |birthday| match is_birthday_member {
+                    Some(true) => format!("{}, wished in this server", birthday.description()),
+                    Some(false) => format!(
+                        "{}, set `birthday_here` to get it wished in this server",
+                        birthday.description()
+                    ),
+                    None => birthday.description(),
+                },
+            ),
+        ))
+        .build()
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_preferences_command(self) -> Result<()> {
+        let user_id = self.interaction.author_id().ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        // check everything first so a bad option doesn't leave the others half saved
+        let work_start = options.work_start.map(TryInto::try_into).transpose()?;
+        let work_end = options.work_end.map(TryInto::try_into).transpose()?;
+        let birthday = match (options.birthday_month, options.birthday_day) {
+            (Some(month), Some(day)) => Some(Birthday::new(month.try_into()?, day.try_into()?)?),
+            (Some(_), None) | (None, Some(_)) => return Err(CustomError::BadDate.into()),
+            (None, None) => None,
+        };
+        if options.birthday_here.is_some() && self.interaction.guild_id.is_none() {
+            return Err(CustomError::NotInServer.into());
+        }
+
+        if let Some(time_resolution) = options.time_resolution {
+            self.ctx
+                .set_user_time_resolution(user_id, Some(time_resolution))
+                .await?;
+        }
+        if options.server_time_resolution == Some(true) {
+            self.ctx.set_user_time_resolution(user_id, None).await?;
+        }
+
+        if let Some(dst_policy) = options.dst_policy {
+            self.ctx.set_user_dst_policy(user_id, dst_policy).await?;
+        }
+
+        if work_start.is_some() || work_end.is_some() || options.days_off.is_some() {
+            self.ctx
+                .set_working_hours(user_id, work_start, work_end, options.days_off)
+                .await?;
+        }
+
+        if birthday.is_some() {
+            self.ctx.set_birthday(user_id, birthday).await?;
+        }
+        if options.forget_birthday == Some(true) {
+            self.ctx.set_birthday(user_id, None).await?;
+        }
+        if let Some(birthday_here) = options.birthday_here {
+            self.ctx
+                .set_birthday_member(
+                    self.interaction.guild_id.ok_or(CustomError::NotInServer)?,
+                    user_id,
+                    birthday_here,
+                )
+                .await?;
+        }
+
+        let time_resolution = self.ctx.user_time_resolution(user_id).await?;
+        let dst_policy = self.ctx.user_dst_policy(user_id).await?;
+        let working_hours = self.ctx.working_hours(user_id).await?;
+        let birthday = self.ctx.birthday(user_id).await?;
+        let is_birthday_member = match self.interaction.guild_id {
+            Some(guild_id) => Some(self.ctx.is_birthday_member(guild_id, user_id).await?),
+            None => None,
+        };
+        self.handle
+            .reply(Reply::new().ephemeral().embed(preferences_embed(
+                time_resolution,
+                dst_policy,
+                working_hours,
+                birthday,
+                is_birthday_member,
+            )))
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Preferences).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use chrono::Utc;
+use sparkle_convenience::{
+    error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::channel::message::{component::ActionRow, Component};
+
+use crate::{
+    database::UsageKind,
+    interaction::{
+        calendar::calendar_button,
+        date::{self, Style},
+        reminder::remind_button,
+        InteractionContext,
+    },
+    time::{self, Recurrence},
+};
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "recurring",
+    desc = "send the next few times something that repeats happens, in everyone's own timezone"
+)]
+pub struct Command {
+    #[command(desc = "how often it happens")]
+    pub every: Recurrence,
+    #[command(
+        desc = "when it first happens, like friday 8pm, now by default",
+        max_length = 100
+    )]
+    pub when: Option<String>,
+    #[command(
+        desc = "how many of the next times to send, 5 by default",
+        min_value = 1,
+        max_value = 10
+    )]
+    pub count: Option<i64>,
+    #[command(desc = "the style of the dates, by default like Tuesday, 20 April 2021 4:20 PM")]
+    pub style: Option<Style>,
+    #[command(
+        desc = "the timezone it happens in, like Asia/Tokyo, yours by default",
+        autocomplete = true
+    )]
+    pub timezone: Option<String>,
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_recurring_command(self) -> Result<()> {
+        let author_id = self.interaction.author_id().ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        let date = date::Command {
+            when: options.when,
+            timezone: options.timezone,
+            ..date::Command::default()
+        };
+        let (start, _) = self.ctx.user_time(author_id, &date).await?;
+
+        let occurrences = options.every.occurrences(
+            start,
+            Utc::now(),
+            options.count.map_or(Ok(5), TryInto::try_into)?,
+        );
+        let mut content = format!("🔁 {}:", options.every.description());
+        for occurrence in occurrences {
+            content.push('\n');
+            content.push_str(&time::format(occurrence, options.style));
+        }
+        content.push_str(&date.timezone_note(start));
+
+        self.handle
+            .reply(
+                Reply::new()
+                    .content(content)
+                    .component(Component::ActionRow(ActionRow {
+                        components: vec![remind_button(), calendar_button()],
+                    })),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Recurring).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use chrono::Utc;
+use sparkle_convenience::{error::IntoError, reply::Reply};
+use twilight_model::channel::message::{
+    component::{Button, ButtonStyle},
+    Component, ReactionType,
+};
+
+use crate::{
+    database::{Reminder, UsageKind},
+    embed,
+    interaction::InteractionContext,
+    time, CustomError,
+};
+
+pub const REMIND_BUTTON_CUSTOM_ID: &str = "reminder_remind_button";
+
+pub fn remind_button() -> Component {
+    Component::Button(Button {
+        custom_id: Some(REMIND_BUTTON_CUSTOM_ID.to_owned()),
+        emoji: Some(ReactionType::Unicode {
+            name: "⏰".to_owned(),
+        }),
+        label: Some("remind me".to_owned()),
+        disabled: false,
+        style: ButtonStyle::Secondary,
+        url: None,
+    })
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_remind_button_click(self) -> Result<()> {
+        let user_id = self.interaction.author_id().ok()?;
+        let message = self.interaction.message.ok()?;
+
+        let now = Utc::now().timestamp();
+        let remind_at = time::timestamps_from_text(&message.content)
+            .into_iter()
+            .filter(|timestamp| *timestamp > now)
+            .min()
+            .ok_or(CustomError::ReminderInPast)?;
+
+        self.ctx
+            .insert_reminder(Reminder {
+                user_id,
+                guild_id: self.interaction.guild_id,
+                channel_id: message.channel_id,
+                message_id: message.id,
+                remind_at,
+            })
+            .await?;
+
+        self.handle
+            .reply(
+                Reply::new().ephemeral().embed(
+                    embed()
+                        .title("⏰ got it")
+                        .description(format!(
+                            "i'll dm you <t:{remind_at}:R>, if your dms are closed i'll ping you \
+                             here instead"
+                        ))
+                        .build(),
+                ),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Reminder).await?;
+        Ok(())
+    }
+}
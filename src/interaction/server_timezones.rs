@@ -0,0 +1,149 @@
+use std::{cmp::Reverse, collections::BTreeMap, fmt::Write};
+
+use anyhow::Result;
+use chrono::{Duration, DurationRound, Timelike, Utc};
+use chrono_tz::Tz;
+use sparkle_convenience::{
+    error::IntoError,
+    interaction::{extract::InteractionDataExt, DeferBehavior, DeferVisibility},
+    reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::{
+    database::UsageKind,
+    embed,
+    interaction::InteractionContext,
+    time::{self, is_working_hour},
+    truncated, EMBED_FIELD_VALUE_LIMIT,
+};
+
+const BAR_WIDTH: usize = 10;
+const AWAKE_START_HOUR: u32 = 10;
+const AWAKE_END_HOUR: u32 = 22;
+const BEST_HOUR_COUNT: usize = 3;
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "server-timezones",
+    desc = "see where everyone in the server is and when the most people are around",
+    dm_permission = false
+)]
+pub struct Command {
+    #[command(desc = "whether everyone can see the reply, only you by default")]
+    pub public: Option<bool>,
+}
+
+fn bar(count: usize, max: usize) -> String {
+    "█".repeat((count * BAR_WIDTH).div_ceil(max.max(1)))
+}
+
+fn region(tz: Tz) -> &'static str {
+    match tz.name().split_once('/') {
+        Some(("Etc", _)) | None => "other",
+        Some((region, _)) => region,
+    }
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_server_timezones_command(self) -> Result<()> {
+        let guild_id = self.interaction.guild_id.ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        // paging through the members can take longer than discord waits for a response
+        self.handle
+            .defer_with_behavior(
+                if options.public == Some(true) {
+                    DeferVisibility::Visible
+                } else {
+                    DeferVisibility::Ephemeral
+                },
+                DeferBehavior::Followup,
+            )
+            .await?;
+        let member_ids = self.ctx.member_ids(guild_id, None).await?;
+        let timezones = self.ctx.timezones(&member_ids).await?;
+
+        let now = Utc::now();
+        let mut embed = embed()
+            .title("📊 where everyone here is")
+            .description(format!(
+                "{} of the {} people here set their timezone",
+                timezones.len(),
+                member_ids.len()
+            ));
+
+        let mut regions = BTreeMap::new();
+        for (_, tz) in &timezones {
+            *regions.entry(region(*tz)).or_insert(0_usize) += 1;
+        }
+
+        let groups = time::group_by_offset(timezones, now);
+        if !groups.is_empty() {
+            let max = groups.iter().map(|group| group.members.len()).max().ok()?;
+            let offsets = groups
+                .iter()
+                .map(|group| {
+                    format!(
+                        "`{}` {} {}\n",
+                        time::format_offset(group.offset),
+                        bar(group.members.len(), max),
+                        group.members.len()
+                    )
+                })
+                .collect::<Vec<_>>();
+            embed = embed.field(EmbedFieldBuilder::new(
+                "by utc offset",
+                truncated(&offsets, EMBED_FIELD_VALUE_LIMIT),
+            ));
+
+            let mut regions = regions.into_iter().collect::<Vec<_>>();
+            regions.sort_by_key(|(_, count)| Reverse(*count));
+            let mut region_lines = String::new();
+            for (region, count) in &regions {
+                writeln!(region_lines, "{region} {count}")?;
+            }
+            embed = embed.field(EmbedFieldBuilder::new("by region", region_lines).inline());
+
+            let this_hour = now.duration_trunc(Duration::hours(1))?;
+            let mut hours = (0..24)
+                .map(|hour| {
+                    let slot = this_hour + Duration::hours(hour);
+                    let awake = groups
+                        .iter()
+                        .flat_map(|group| &group.members)
+                        .filter(|(_, tz)| {
+                            is_working_hour(
+                                slot.with_timezone(tz).hour(),
+                                AWAKE_START_HOUR,
+                                AWAKE_END_HOUR,
+                            )
+                        })
+                        .count();
+                    (slot, awake)
+                })
+                .collect::<Vec<_>>();
+            hours.sort_by_key(|(slot, awake)| (Reverse(*awake), *slot));
+            let mut best_hours = String::new();
+            for (slot, awake) in hours.iter().take(BEST_HOUR_COUNT) {
+                writeln!(
+                    best_hours,
+                    "<t:{}:t> — {awake} people are up",
+                    slot.timestamp()
+                )?;
+            }
+            embed = embed.field(EmbedFieldBuilder::new("best times to post", best_hours).inline());
+        }
+
+        let mut reply = Reply::new().embed(embed.build());
+        if options.public != Some(true) {
+            reply = reply.ephemeral();
+        }
+        self.handle.reply(reply).await?;
+
+        self.ctx.insert_usage(UsageKind::ServerTimezones).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use sparkle_convenience::{
+    error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::{
+    channel::message::Embed,
+    guild::Permissions,
+    id::{marker::ChannelMarker, Id},
+};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::{
+    birthday::DEFAULT_BIRTHDAY_MESSAGE,
+    database::{BirthdayChannel, GuildSettings, UsageKind},
+    embed,
+    interaction::InteractionContext,
+    time::TimeResolution,
+};
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "settings",
+    desc = "change how i work in this server",
+    default_permissions = "default_permissions",
+    dm_permission = false
+)]
+pub struct Command {
+    #[command(
+        desc = "whether messages i repost with converted times ping the people they pinged again"
+    )]
+    pub repost_pings: Option<bool>,
+    #[command(desc = "which day i should use when converting a time without a date")]
+    pub time_resolution: Option<TimeResolution>,
+    #[command(
+        desc = "the channel i wish people a happy birthday in at midnight their time",
+        channel_types = "guild_text guild_announcement"
+    )]
+    pub birthday_channel: Option<Id<ChannelMarker>>,
+    #[command(
+        desc = "what i say on someone's birthday, {user} is replaced with their mention",
+        max_length = 1000
+    )]
+    pub birthday_message: Option<String>,
+    #[command(
+        desc = "whether to stop wishing people a happy birthday, pick a channel to start again"
+    )]
+    pub stop_birthdays: Option<bool>,
+}
+
+const fn default_permissions() -> Permissions {
+    Permissions::MANAGE_GUILD
+}
+
+fn settings_embed(settings: GuildSettings, birthday_channel: Option<BirthdayChannel>) -> Embed {
+    embed()
+        .title("⚙️ here's how i work in this server")
+        .field(EmbedFieldBuilder::new(
+            "repost pings",
+            if settings.repost_pings {
+                "on, reposted messages ping the people and roles the original message pinged again"
+            } else {
+                "off, mentions in reposted messages still show but nobody gets pinged twice"
+            },
+        ))
+        .field(EmbedFieldBuilder::new(
+            "time resolution",
+            settings.time_resolution.description(),
+        ))
+        .field(EmbedFieldBuilder::new(
+            "birthdays",
+            birthday_channel.map_or_else(
+                || "off, pick a `birthday_channel` to wish people a happy birthday".to_owned(),
+                |channel| {
+                    format!(
+                        "on, in <#{}> saying: {}",
+                        channel.channel_id,
+                        channel
+                            .message
+                            .as_deref()
+                            .unwrap_or(DEFAULT_BIRTHDAY_MESSAGE)
+                    )
+                },
+            ),
+        ))
+        .build()
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_settings_command(self) -> Result<()> {
+        let guild_id = self.interaction.guild_id.ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        if let Some(repost_pings) = options.repost_pings {
+            self.ctx.set_repost_pings(guild_id, repost_pings).await?;
+        }
+        if let Some(time_resolution) = options.time_resolution {
+            self.ctx
+                .set_time_resolution(guild_id, time_resolution)
+                .await?;
+        }
+
+        if let Some(birthday_channel) = options.birthday_channel {
+            self.ctx
+                .set_birthday_channel(guild_id, Some(birthday_channel))
+                .await?;
+        }
+        if let Some(birthday_message) = &options.birthday_message {
+            self.ctx
+                .set_birthday_message(guild_id, birthday_message)
+                .await?;
+        }
+        if options.stop_birthdays == Some(true) {
+            self.ctx.set_birthday_channel(guild_id, None).await?;
+        }
+
+        let settings = self.ctx.guild_settings(guild_id).await?;
+        let birthday_channel = self.ctx.birthday_channel(guild_id).await?;
+        self.handle
+            .reply(
+                Reply::new()
+                    .ephemeral()
+                    .embed(settings_embed(settings, birthday_channel)),
+            )
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::Settings).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::Result;
+use chrono::Utc;
+use sparkle_convenience::{
+    error::IntoError,
+    interaction::{extract::InteractionDataExt, DeferBehavior, DeferVisibility},
+    reply::Reply,
+};
+use twilight_interactions::command::{CommandModel, CreateCommand};
+use twilight_model::id::{
+    marker::{GuildMarker, RoleMarker, UserMarker},
+    Id,
+};
+use twilight_util::builder::embed::EmbedFieldBuilder;
+
+use crate::{
+    database::UsageKind, embed, interaction::InteractionContext, time, truncated, Context,
+    EMBED_FIELD_LIMIT, EMBED_FIELD_VALUE_LIMIT, EMBED_FOOTER, EMBED_LENGTH_LIMIT,
+    TRUNCATION_RESERVE,
+};
+
+const MEMBER_LIMIT: u16 = 1000;
+const TITLE: &str = "🌍 what time it is for everyone";
+
+#[derive(CommandModel, CreateCommand)]
+#[command(
+    name = "team-time",
+    desc = "see what time it is for everyone in the server or a role",
+    dm_permission = false
+)]
+pub struct Command {
+    #[command(desc = "only show the people with this role")]
+    pub role: Option<Id<RoleMarker>>,
+}
+
+impl Context {
+    pub async fn member_ids(
+        &self,
+        guild_id: Id<GuildMarker>,
+        role_id: Option<Id<RoleMarker>>,
+    ) -> Result<Vec<Id<UserMarker>>> {
+        let mut member_ids = vec![];
+        let mut after = None;
+        loop {
+            let mut request = self.bot.http.guild_members(guild_id).limit(MEMBER_LIMIT)?;
+            if let Some(after) = after {
+                request = request.after(after);
+            }
+            let members = request.await?.models().await?;
+
+            after = members.last().map(|member| member.user.id);
+            member_ids.extend(
+                members
+                    .iter()
+                    .filter(|member| {
+                        !member.user.bot
+                            && role_id.iter().all(|role_id| member.roles.contains(role_id))
+                    })
+                    .map(|member| member.user.id),
+            );
+
+            if members.len() < usize::from(MEMBER_LIMIT) {
+                return Ok(member_ids);
+            }
+        }
+    }
+}
+
+impl InteractionContext<'_> {
+    pub async fn handle_team_time_command(self) -> Result<()> {
+        let guild_id = self.interaction.guild_id.ok()?;
+        let options =
+            Command::from_interaction(self.interaction.data.ok()?.command().ok()?.into())?;
+
+        // paging through the members can take longer than discord waits for a response
+        self.handle
+            .defer_with_behavior(DeferVisibility::Ephemeral, DeferBehavior::Followup)
+            .await?;
+        let member_ids = self.ctx.member_ids(guild_id, options.role).await?;
+
+        let now = Utc::now();
+        let groups = time::group_by_offset(self.ctx.timezones(&member_ids).await?, now);
+
+        let mut embed = embed().title(TITLE);
+        if groups.is_empty() {
+            embed = embed.description("nobody here has set their timezone yet");
+        }
+        let mut length = TITLE.len();
+        for (group_idx, group) in groups.iter().enumerate() {
+            let lines = group
+                .members
+                .iter()
+                .map(|(user_id, tz)| format!("<@{user_id}> {}\n", tz.name()))
+                .collect::<Vec<_>>();
+            let value = truncated(&lines, EMBED_FIELD_VALUE_LIMIT);
+
+            let (_, first_tz) = group.members.first().ok()?;
+            let name = format!(
+                "{} · {}",
+                now.with_timezone(first_tz).format("%A %-I:%M %p"),
+                time::format_offset(group.offset)
+            );
+
+            // leave room for the footer and a field saying how many people didn't fit
+            length += name.len() + value.len();
+            if (group_idx + 1 == EMBED_FIELD_LIMIT && group_idx + 1 < groups.len())
+                || length > EMBED_LENGTH_LIMIT - EMBED_FOOTER.len() - TRUNCATION_RESERVE
+            {
+                let remaining = groups[group_idx..]
+                    .iter()
+                    .map(|group| group.members.len())
+                    .sum::<usize>();
+                embed = embed.field(EmbedFieldBuilder::new(
+                    "…",
+                    format!("and {remaining} more people"),
+                ));
+                break;
+            }
+
+            embed = embed.field(EmbedFieldBuilder::new(name, value));
+        }
+
+        self.handle
+            .reply(Reply::new().ephemeral().embed(embed.build()))
+            .await?;
+
+        self.ctx.insert_usage(UsageKind::TeamTime).await?;
+        Ok(())
+    }
+}
@@ -1,12 +1,18 @@
 use anyhow::Result;
-use chrono_tz::Tz;
+use chrono_tz::{Tz, TZ_VARIANTS};
 use sparkle_convenience::{
     error::IntoError, interaction::extract::InteractionDataExt, reply::Reply,
 };
 use twilight_interactions::command::CreateCommand;
-use twilight_model::channel::message::{
-    component::{ActionRow, Button, ButtonStyle, TextInput, TextInputStyle},
-    Component, Embed, ReactionType,
+use twilight_model::{
+    application::{
+        command::{CommandOptionChoice, CommandOptionChoiceValue},
+        interaction::application_command::CommandOptionValue,
+    },
+    channel::message::{
+        component::{ActionRow, Button, ButtonStyle, TextInput, TextInputStyle},
+        Component, Embed, ReactionType,
+    },
 };
 use twilight_util::builder::embed::{EmbedFieldBuilder, EmbedFooterBuilder, ImageSource};
 
@@ -301,4 +307,40 @@ impl InteractionContext<'_> {
 
         Ok(())
     }
+
+    pub async fn handle_timezone_autocomplete(self) -> Result<()> {
+        let input = self
+            .interaction
+            .data
+            .ok()?
+            .command()
+            .ok()?
+            .options
+            .into_iter()
+            .find_map(|option| {
+                if let CommandOptionValue::Focused(value, _) = option.value {
+                    Some(value)
+                } else {
+                    None
+                }
+            })
+            .ok()?
+            .to_lowercase()
+            .replace(' ', "_");
+
+        let choices = TZ_VARIANTS
+            .iter()
+            .map(|tz| tz.name())
+            .filter(|name| name.to_lowercase().contains(&input))
+            .take(25)
+            .map(|name| CommandOptionChoice {
+                name: name.to_owned(),
+                name_localizations: None,
+                value: CommandOptionChoiceValue::String(name.to_owned()),
+            })
+            .collect();
+        self.handle.autocomplete(choices).await?;
+
+        Ok(())
+    }
 }
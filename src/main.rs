@@ -1,7 +1,13 @@
 #![warn(clippy::nursery, clippy::pedantic)]
 #![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
 
-use std::{env, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Write,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use anyhow::Result;
 use dotenvy::dotenv;
@@ -33,10 +39,15 @@ use crate::{
     metrics::{JsonStorageClient, Metrics},
 };
 
+mod birthday;
+mod clock;
+mod countdown;
 mod database;
+mod ics;
 mod interaction;
 mod message;
 mod metrics;
+mod reminder;
 mod time;
 
 const LOGGING_CHANNEL_ID: Id<ChannelMarker> = Id::new(1_002_953_459_890_397_287);
@@ -54,6 +65,14 @@ const REQUIRED_PERMISSIONS: Permissions = Permissions::MANAGE_WEBHOOKS
     .union(Permissions::READ_MESSAGE_HISTORY)
     .union(Permissions::ADD_REACTIONS);
 
+const EMBED_FOOTER: &str = "wondering anything? check out the link in my bio!";
+const EMBED_DESCRIPTION_LIMIT: usize = 4096;
+const EMBED_FIELD_VALUE_LIMIT: usize = 1024;
+const EMBED_FIELD_LIMIT: usize = 25;
+const EMBED_LENGTH_LIMIT: usize = 6000;
+/// room left for the line saying how many items didn't fit
+const TRUNCATION_RESERVE: usize = 32;
+
 #[derive(Clone, Debug, Eq, PartialEq, thiserror::Error)]
 pub enum Error {
     #[error("metrics weren't updated: put: {put:?}, got: {get:?}")]
@@ -68,6 +87,8 @@ pub enum Error {
     Hour12InvalidSuffix { hour: u32, suffix: String },
     #[error("message without a time has time detect reaction by the bot")]
     FalseTimeDetectReaction,
+    #[error("channel parents cache mutex is poisoned")]
+    ChannelParentsPoisoned,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, thiserror::Error)]
@@ -78,6 +99,11 @@ pub enum CustomError {
         if you're sure the timezone is right, please join the support server"
     )]
     BadTimezone,
+    #[error(
+        "i don't know where that is :earth_africa:\n\
+        try a city like `tokyo`, a timezone like `Europe/Berlin` or an offset like `UTC+3`"
+    )]
+    BadLocation,
     #[error(
         "bad news, i need to know your timezone first :scream:\n\
         good news, its really easy to tell me :relieved:\n\
@@ -99,6 +125,66 @@ pub enum CustomError {
         or maybe it is idk, join the support server if it is!"
     )]
     BadDate,
+    #[error(
+        "i couldn't figure out when that is :face_with_spiral_eyes:\n\
+        try something like `tomorrow 5pm`, `next friday noon`, `in 3 days` or `2026-12-31 23:59`"
+    )]
+    BadWhen,
+    #[error(
+        "that ends before it starts :upside_down:\n\
+        make sure the end is after the date, like `7pm` or `in 2 hours`"
+    )]
+    EndBeforeStart,
+    #[error(
+        "that's not your message :face_with_raised_eyebrow:\n\
+        only the person that sent it can do that"
+    )]
+    NotConvertedMessageAuthor,
+    #[error(
+        "that's not a message i converted :thinking:\n\
+        i can only do that for messages i sent after you hit the reaction"
+    )]
+    NotConvertedMessage,
+    #[error(
+        "too late to undo that :hourglass:\n\
+        you can only undo a conversion for a few minutes after it happens"
+    )]
+    UndoExpired,
+    #[error(
+        "there's nothing left to remind you about :sleeping:\n\
+        all the times in that message already passed"
+    )]
+    ReminderInPast,
+    #[error(
+        "that already happened :hourglass:\n\
+        i can only count down to something in the future"
+    )]
+    CountdownInPast,
+    #[error(
+        "that event is gone :ghost:\n\
+        it might be from before events were a thing, try making a new one"
+    )]
+    NotEvent,
+    #[error(
+        "there's no time in that message :mag:\n\
+        i can only add the times i sent to your calendar"
+    )]
+    NoTimeInMessage,
+    #[error(
+        "who's meeting? :busts_in_silhouette:\n\
+        mention some people or pick a role to plan a meeting with"
+    )]
+    NoMeetingMembers,
+    #[error(
+        "that's too many clocks :alarm_clock:\n\
+        i can keep up to 10 of them going, separate the places with commas"
+    )]
+    TooManyClocks,
+    #[error(
+        "that only works in a server :house:\n\
+        use it in the server you want it for"
+    )]
+    NotInServer,
 }
 
 #[derive(Debug)]
@@ -108,6 +194,7 @@ pub struct Context {
     db: PgPool,
     json_storage: JsonStorageClient,
     command_ids: CommandIds,
+    channel_parents: Mutex<HashMap<Id<ChannelMarker>, Option<Id<ChannelMarker>>>>,
 }
 
 impl Context {
@@ -153,8 +240,11 @@ async fn main() -> Result<()> {
             url: env::var("METRICS_URL")?,
         },
         command_ids,
+        channel_parents: Mutex::new(HashMap::new()),
     });
 
+    ctx.clear_all_original_contents().await?;
+
     let mut metrics_update_interval = tokio::time::interval(Duration::from_secs(60 * 60));
     let ctx_metrics_ref = Arc::clone(&ctx);
     tokio::spawn(async move {
@@ -166,6 +256,8 @@ async fn main() -> Result<()> {
         }
     });
 
+    spawn_scheduled_tasks(&ctx);
+
     let mut events = ShardEventStream::new(shards.iter_mut());
     while let Some((_, event_res)) = events.next().await {
         let ctx_event_ref = Arc::clone(&ctx);
@@ -194,12 +286,83 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+fn spawn_scheduled_tasks(ctx: &Arc<Context>) {
+    let mut reminders_interval = tokio::time::interval(Duration::from_secs(30));
+    let ctx_reminders_ref = Arc::clone(ctx);
+    tokio::spawn(async move {
+        loop {
+            reminders_interval.tick().await;
+            if let Err(err) = ctx_reminders_ref.send_due_reminders().await {
+                ctx_reminders_ref.bot.log(err).await;
+            }
+        }
+    });
+
+    let mut birthdays_interval = tokio::time::interval(Duration::from_secs(60));
+    let ctx_birthdays_ref = Arc::clone(ctx);
+    tokio::spawn(async move {
+        loop {
+            birthdays_interval.tick().await;
+            if let Err(err) = ctx_birthdays_ref.announce_birthdays().await {
+                ctx_birthdays_ref.bot.log(err).await;
+            }
+        }
+    });
+
+    let mut countdowns_interval = tokio::time::interval(Duration::from_secs(30));
+    let ctx_countdowns_ref = Arc::clone(ctx);
+    tokio::spawn(async move {
+        loop {
+            countdowns_interval.tick().await;
+            if let Err(err) = ctx_countdowns_ref.update_due_countdowns().await {
+                ctx_countdowns_ref.bot.log(err).await;
+            }
+        }
+    });
+
+    let mut clock_messages_interval = tokio::time::interval(Duration::from_secs(60));
+    let ctx_clock_messages_ref = Arc::clone(ctx);
+    tokio::spawn(async move {
+        loop {
+            clock_messages_interval.tick().await;
+            if let Err(err) = ctx_clock_messages_ref.update_clock_messages().await {
+                ctx_clock_messages_ref.bot.log(err).await;
+            }
+        }
+    });
+
+    // discord only allows renaming a channel twice every 10 minutes
+    let mut clock_channels_interval = tokio::time::interval(Duration::from_secs(10 * 60));
+    let ctx_clock_channels_ref = Arc::clone(ctx);
+    tokio::spawn(async move {
+        loop {
+            clock_channels_interval.tick().await;
+            if let Err(err) = ctx_clock_channels_ref.update_clock_channels().await {
+                ctx_clock_channels_ref.bot.log(err).await;
+            }
+        }
+    });
+}
+
 fn embed() -> EmbedBuilder {
     EmbedBuilder::new()
         .color(0x00d4_f1f9)
-        .footer(EmbedFooterBuilder::new(
-            "wondering anything? check out the link in my bio!",
-        ))
+        .footer(EmbedFooterBuilder::new(EMBED_FOOTER))
+}
+
+/// join the items until the next one wouldn't fit in `limit`, ending with a
+/// line saying how many are left out
+fn truncated(items: &[String], limit: usize) -> String {
+    let mut joined = String::new();
+    for (idx, item) in items.iter().enumerate() {
+        if joined.len() + item.len() + TRUNCATION_RESERVE > limit {
+            let _ = write!(joined, "…and {} more", items.len() - idx);
+            break;
+        }
+        joined.push_str(item);
+    }
+
+    joined
 }
 
 fn err_reply(err: &anyhow::Error) -> Reply {
@@ -230,9 +393,22 @@ fn _err_reply(err: &anyhow::Error, is_timed: bool) -> Reply {
         }
 
         if let CustomError::BadTimezone
+        | CustomError::BadLocation
         | CustomError::MissingTimezone(_)
         | CustomError::MessageTooLong
-        | CustomError::BadDate = custom_err
+        | CustomError::BadDate
+        | CustomError::BadWhen
+        | CustomError::EndBeforeStart
+        | CustomError::NotConvertedMessageAuthor
+        | CustomError::NotConvertedMessage
+        | CustomError::UndoExpired
+        | CustomError::ReminderInPast
+        | CustomError::CountdownInPast
+        | CustomError::NotEvent
+        | CustomError::NoTimeInMessage
+        | CustomError::NoMeetingMembers
+        | CustomError::TooManyClocks
+        | CustomError::NotInServer = custom_err
         {
             reply = reply.ephemeral();
         };
@@ -1,33 +1,48 @@
-use std::{fmt::Write, future::IntoFuture, time::Duration};
+use std::{fmt::Write, time::Duration};
 
 use anyhow::Result;
-use chrono::{offset::Local, Datelike, TimeZone};
+use chrono::Utc;
 use sparkle_convenience::{
-    error::{ErrorExt, IntoError},
+    error::{extract::HttpErrorExt, ErrorExt, IntoError},
     message::HttpExt,
 };
-use twilight_http::{
-    request::channel::reaction::RequestReactionType,
-    response::{marker::EmptyBody, ResponseFuture},
-    Response,
-};
+use twilight_http::{request::channel::reaction::RequestReactionType, Response};
 use twilight_model::{
-    channel::{message::ReactionType, Message},
+    channel::{
+        message::{component::ActionRow, AllowedMentions, Component, MentionType, ReactionType},
+        Message,
+    },
     gateway::{payload::incoming::MessageUpdate, GatewayReaction},
     guild::Member,
     id::{
-        marker::{GuildMarker, UserMarker},
+        marker::{ChannelMarker, GuildMarker, UserMarker},
         Id,
     },
     util::ImageHash,
 };
-use twilight_util::builder::embed::{EmbedAuthorBuilder, EmbedFooterBuilder, ImageSource};
+use twilight_util::builder::embed::{
+    EmbedAuthorBuilder, EmbedFieldBuilder, EmbedFooterBuilder, ImageSource,
+};
 
 use crate::{
-    database::UsageKind, embed, err_reply_timed, time::ParsedTime, Context, CustomError, Error,
+    database::{UsageKind, Webhook},
+    embed, err_reply_timed,
+    interaction::{
+        calendar::calendar_button, converted_message::undo_button, reminder::remind_button,
+    },
+    time::{LocalTimeNote, ParsedTime, TimeResolution},
+    Context, CustomError, Error,
 };
 
 const TIME_DETECT_EMOJI: &str = "⏰";
+const DELETE_EMOJI: &str = "🗑️";
+
+const UNKNOWN_WEBHOOK_ERROR_CODE: u64 = 10015;
+pub const UNKNOWN_CHANNEL_ERROR_CODE: u64 = 10003;
+pub const UNKNOWN_MESSAGE_ERROR_CODE: u64 = 10008;
+
+const MAX_CACHED_CHANNEL_PARENTS: usize = 10_000;
+const UNDO_DURATION: Duration = Duration::from_secs(60 * 2);
 
 impl Context {
     pub async fn handle_message(&self, message: Message) {
@@ -53,7 +68,11 @@ impl Context {
     pub async fn handle_reaction(&self, reaction: GatewayReaction) {
         let channel_id = reaction.channel_id;
 
-        let reaction_handle_result = self.handle_time_reaction(reaction).await;
+        let reaction_handle_result = if is_delete(&reaction.emoji) {
+            self.handle_delete_reaction(reaction).await
+        } else {
+            self.handle_time_reaction(reaction).await
+        };
 
         if let Err(err) = reaction_handle_result {
             if let Some(err_response) = self
@@ -62,7 +81,7 @@ impl Context {
                 .await
             {
                 if let Err(Some(delete_err)) = self
-                    .delete_err_response(err_response)
+                    .delete_timed_response(err_response)
                     .await
                     .map_err(ErrorExt::internal::<CustomError>)
                 {
@@ -72,7 +91,7 @@ impl Context {
         }
     }
 
-    async fn delete_err_response(&self, response: Response<Message>) -> Result<()> {
+    async fn delete_timed_response(&self, response: Response<Message>) -> Result<()> {
         let (channel_id, message_id) = {
             let message = response.model().await?;
             (message.channel_id, message.id)
@@ -172,7 +191,14 @@ impl Context {
             return Err(Error::FalseTimeDetectReaction.into());
         }
 
-        self.convert_message(&mut message, &parsed_times)
+        let original_content = message.content.clone();
+        let (content, notes) = self
+            .convert_content(
+                message.author.id,
+                message.guild_id,
+                &message.content,
+                &parsed_times,
+            )
             .await
             .map_err(|err| match err.downcast_ref() {
                 Some(CustomError::MissingTimezone(command_id))
@@ -182,20 +208,11 @@ impl Context {
                 }
                 _ => err,
             })?;
+        message.content = content;
 
         if reaction.user_id == message.author.id {
-            let exec_webhook = self
-                .execute_webhook_as_member(&message, &reaction_member)
-                .await?;
-
-            self.bot
-                .http
-                .delete_message(message.channel_id, message.id)
+            self.replace_time_message(&message, &reaction_member, &original_content, &notes)
                 .await?;
-
-            exec_webhook.await?;
-
-            self.insert_usage(UsageKind::TimeConvertByAuthor).await?;
         } else {
             let guild_id = reaction.guild_id.ok()?;
             let member = self
@@ -206,15 +223,9 @@ impl Context {
                 .model()
                 .await?;
 
-            self.bot
-                .http
-                .dm_user(reaction.user_id)
-                .await?
-                .embeds(&[embed()
-                    .author(
-                        EmbedAuthorBuilder::new(
-                            member.nick.as_ref().unwrap_or(&message.author.name),
-                        )
+            let mut embed = embed()
+                .author(
+                    EmbedAuthorBuilder::new(member.nick.as_ref().unwrap_or(&message.author.name))
                         .icon_url(ImageSource::url(avatar_url(
                             member.avatar,
                             message.author.avatar,
@@ -222,13 +233,24 @@ impl Context {
                             Some(guild_id),
                             message.author.discriminator,
                         ))?),
-                    )
-                    .description(&message.content)
-                    .footer(EmbedFooterBuilder::new(
-                        "if the person that sent the message reacts, i can also replace the \
-                         original message!",
-                    ))
-                    .build()])?
+                )
+                .description(&message.content)
+                .footer(EmbedFooterBuilder::new(
+                    "if the person that sent the message reacts, i can also replace the \
+                     original message!",
+                ));
+            if !notes.is_empty() {
+                embed = embed.field(EmbedFieldBuilder::new(
+                    "⚠️ heads up",
+                    notes_description(&notes),
+                ));
+            }
+
+            self.bot
+                .http
+                .dm_user(reaction.user_id)
+                .await?
+                .embeds(&[embed.build()])?
                 .await?;
 
             self.insert_usage(UsageKind::TimeConvertByNonAuthor).await?;
@@ -237,76 +259,160 @@ impl Context {
         Ok(())
     }
 
-    async fn convert_message(
+    async fn replace_time_message(
         &self,
-        message: &mut Message,
-        parsed_times: &[ParsedTime],
+        message: &Message,
+        member: &Member,
+        original_content: &str,
+        notes: &[LocalTimeNote],
     ) -> Result<()> {
-        let now = Local::now();
-        let Some(tz) = self.timezone(message.author.id).await? else {
+        let webhook_message = self.execute_webhook_as_member(message, member).await?;
+        self.insert_converted_message(
+            webhook_message.id,
+            message.author.id,
+            Some(original_content),
+        )
+        .await?;
+
+        self.bot
+            .http
+            .delete_message(message.channel_id, message.id)
+            .await?;
+
+        self.insert_usage(UsageKind::TimeConvertByAuthor).await?;
+
+        let (notes_result, expire_result) = tokio::join!(
+            self.send_timed_notes(message.channel_id, notes),
+            self.expire_undo(&webhook_message)
+        );
+        notes_result?;
+        expire_result?;
+
+        Ok(())
+    }
+
+    async fn handle_delete_reaction(&self, reaction: GatewayReaction) -> Result<()> {
+        let Some(converted_message) = self.converted_message(reaction.message_id).await? else {
+            return Ok(());
+        };
+        if converted_message.author_id != reaction.user_id {
+            return Ok(());
+        }
+
+        self.remove_converted_message(reaction.channel_id, reaction.message_id)
+            .await?;
+
+        self.insert_usage(UsageKind::TimeConvertDelete).await?;
+        Ok(())
+    }
+
+    pub async fn convert_content(
+        &self,
+        author_id: Id<UserMarker>,
+        guild_id: Option<Id<GuildMarker>>,
+        content: &str,
+        parsed_times: &[ParsedTime],
+    ) -> Result<(String, Vec<LocalTimeNote>)> {
+        let now = Utc::now();
+        let Some(tz) = self.timezone(author_id).await? else {
             return Err(CustomError::MissingTimezone(self.command_ids.timezone).into());
         };
+        let time_resolution = match (self.user_time_resolution(author_id).await?, guild_id) {
+            (Some(time_resolution), _) => time_resolution,
+            (None, Some(guild_id)) => self.guild_settings(guild_id).await?.time_resolution,
+            (None, None) => TimeResolution::default(),
+        };
+
+        let dst_policy = self.user_dst_policy(author_id).await?;
 
-        let mut content = String::new();
+        let mut converted = String::new();
+        let mut notes = vec![];
         let mut push_start = 0;
         for time in parsed_times {
+            let (date_time, note) = time.resolve(tz, now, time_resolution, dst_policy)?;
+            if let Some(note) = note {
+                if !notes.contains(&note) {
+                    notes.push(note);
+                }
+            }
+
             write!(
-                content,
+                converted,
                 "{}<t:{}:t>",
-                message.content.get(push_start..time.range.start).ok()?,
-                tz.with_ymd_and_hms(now.year(), now.month(), now.day(), time.hour, time.min, 0)
-                    .single()
-                    .ok()?
-                    .timestamp()
+                content.get(push_start..time.range.start).ok()?,
+                date_time.timestamp()
             )?;
             push_start = time.range.end;
         }
-        content.push_str(message.content.get(push_start..).ok()?);
-        message.content = content;
+        converted.push_str(content.get(push_start..).ok()?);
 
-        Ok(())
+        Ok((converted, notes))
     }
 
-    async fn execute_webhook_as_member(
+    async fn send_timed_notes(
         &self,
-        message: &Message,
-        member: &Member,
-    ) -> Result<ResponseFuture<EmptyBody>> {
-        let mut channel_id = message.channel_id;
-        let mut thread_id = None;
-        let channel = self
+        channel_id: Id<ChannelMarker>,
+        notes: &[LocalTimeNote],
+    ) -> Result<()> {
+        if notes.is_empty() {
+            return Ok(());
+        }
+
+        let response = self
             .bot
             .http
-            .channel(message.channel_id)
-            .await?
-            .model()
+            .create_message(channel_id)
+            .embeds(&[embed()
+                .title("⚠️ heads up")
+                .description(notes_description(notes))
+                .footer(EmbedFooterBuilder::new(
+                    "🧨 this message will self destruct in a minute",
+                ))
+                .build()])?
             .await?;
-        if channel.kind.is_thread() {
-            channel_id = channel.parent_id.ok()?;
-            thread_id = Some(message.channel_id);
-        };
 
-        let webhook = match self
-            .bot
-            .http
-            .channel_webhooks(channel_id)
-            .await?
-            .models()
-            .await?
-            .into_iter()
-            .find(|webhook| webhook.token.is_some())
+        self.delete_timed_response(response).await
+    }
+
+    async fn execute_webhook_as_member(
+        &self,
+        message: &Message,
+        member: &Member,
+    ) -> Result<Message> {
+        let (channel_id, thread_id) = self.webhook_channel(message.channel_id).await?;
+
+        let webhook = self.webhook_for_channel(channel_id).await?;
+        match self
+            .execute_webhook_for_member(&webhook, thread_id, message, member)
+            .await
         {
-            Some(webhook) => webhook,
-            None => {
-                self.bot
-                    .http
-                    .create_webhook(channel_id, "time sender")?
-                    .await?
-                    .model()
-                    .await?
+            Err(err)
+                if err
+                    .downcast_ref::<twilight_http::Error>()
+                    .and_then(HttpErrorExt::code)
+                    == Some(UNKNOWN_WEBHOOK_ERROR_CODE) =>
+            {
+                self.delete_webhook(channel_id).await?;
+
+                let webhook = self.webhook_for_channel(channel_id).await?;
+                self.execute_webhook_for_member(&webhook, thread_id, message, member)
+                    .await
             }
-        };
-        let webhook_token = webhook.token.ok()?;
+            res => res,
+        }
+    }
+
+    async fn execute_webhook_for_member(
+        &self,
+        webhook: &Webhook,
+        thread_id: Option<Id<ChannelMarker>>,
+        message: &Message,
+        member: &Member,
+    ) -> Result<Message> {
+        let allowed_mentions = self.repost_allowed_mentions(message).await?;
+        let components = [Component::ActionRow(ActionRow {
+            components: vec![undo_button(), remind_button(), calendar_button()],
+        })];
 
         let username = member
             .nick
@@ -321,9 +427,11 @@ impl Context {
         let mut execute_webhook = self
             .bot
             .http
-            .execute_webhook(webhook.id, &webhook_token)
+            .execute_webhook(webhook.id, &webhook.token)
             .content(&message.content)
             .map_err(|_| CustomError::MessageTooLong)?
+            .allowed_mentions(Some(&allowed_mentions))
+            .components(&components)?
             .username(&username)?;
 
         if let Some(thread_id) = thread_id {
@@ -338,7 +446,144 @@ impl Context {
                 message.guild_id,
                 message.author.discriminator,
             ))
-            .into_future())
+            .wait()
+            .await?
+            .model()
+            .await?)
+    }
+
+    async fn expire_undo(&self, webhook_message: &Message) -> Result<()> {
+        tokio::time::sleep(UNDO_DURATION).await;
+
+        // the message was undone or deleted in the meantime
+        if self
+            .converted_message(webhook_message.id)
+            .await?
+            .and_then(|converted_message| converted_message.original_content)
+            .is_none()
+        {
+            return Ok(());
+        }
+        self.clear_original_content(webhook_message.id).await?;
+
+        let (channel_id, thread_id) = self.webhook_channel(webhook_message.channel_id).await?;
+        let Some(webhook) = self.webhook(channel_id).await? else {
+            return Ok(());
+        };
+
+        let components = [Component::ActionRow(ActionRow {
+            components: vec![remind_button(), calendar_button()],
+        })];
+        let mut update_webhook_message = self
+            .bot
+            .http
+            .update_webhook_message(webhook.id, &webhook.token, webhook_message.id)
+            .components(Some(&components))?;
+        if let Some(thread_id) = thread_id {
+            update_webhook_message = update_webhook_message.thread_id(thread_id);
+        }
+        match update_webhook_message.await {
+            Err(err) if err.code() == Some(UNKNOWN_MESSAGE_ERROR_CODE) => Ok(()),
+            Err(err) => Err(err.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    pub async fn webhook_channel(
+        &self,
+        channel_id: Id<ChannelMarker>,
+    ) -> Result<(Id<ChannelMarker>, Option<Id<ChannelMarker>>)> {
+        let cached_parent_id = self
+            .channel_parents
+            .lock()
+            .map_err(|_| Error::ChannelParentsPoisoned)?
+            .get(&channel_id)
+            .copied();
+
+        let parent_id = if let Some(parent_id) = cached_parent_id {
+            parent_id
+        } else {
+            let channel = self.bot.http.channel(channel_id).await?.model().await?;
+            let parent_id = if channel.kind.is_thread() {
+                Some(channel.parent_id.ok()?)
+            } else {
+                None
+            };
+
+            let mut channel_parents = self
+                .channel_parents
+                .lock()
+                .map_err(|_| Error::ChannelParentsPoisoned)?;
+            // deleted channels never leave the cache, so start over once it gets big
+            if channel_parents.len() >= MAX_CACHED_CHANNEL_PARENTS {
+                channel_parents.clear();
+            }
+            channel_parents.insert(channel_id, parent_id);
+            drop(channel_parents);
+
+            parent_id
+        };
+
+        Ok(parent_id.map_or((channel_id, None), |parent_id| {
+            (parent_id, Some(channel_id))
+        }))
+    }
+
+    pub async fn webhook_for_channel(&self, channel_id: Id<ChannelMarker>) -> Result<Webhook> {
+        if let Some(webhook) = self.webhook(channel_id).await? {
+            return Ok(webhook);
+        }
+
+        let webhook = match self
+            .bot
+            .http
+            .channel_webhooks(channel_id)
+            .await?
+            .models()
+            .await?
+            .into_iter()
+            .find(|webhook| {
+                webhook.application_id == Some(self.bot.application.id) && webhook.token.is_some()
+            }) {
+            Some(webhook) => webhook,
+            None => {
+                self.bot
+                    .http
+                    .create_webhook(channel_id, "time sender")?
+                    .await?
+                    .model()
+                    .await?
+            }
+        };
+        let webhook = Webhook {
+            id: webhook.id,
+            token: webhook.token.ok()?,
+        };
+
+        self.insert_webhook(channel_id, &webhook).await?;
+
+        Ok(webhook)
+    }
+
+    async fn repost_allowed_mentions(&self, message: &Message) -> Result<AllowedMentions> {
+        let Some(guild_id) = message.guild_id else {
+            return Ok(AllowedMentions::default());
+        };
+
+        if !self.guild_settings(guild_id).await?.repost_pings {
+            return Ok(AllowedMentions::default());
+        }
+
+        Ok(AllowedMentions {
+            parse: if message.mention_everyone {
+                vec![MentionType::Everyone]
+            } else {
+                vec![]
+            },
+            users: message.mentions.iter().map(|mention| mention.id).collect(),
+            roles: message.mention_roles.clone(),
+            replied_user: false,
+        })
     }
 }
 
@@ -380,3 +625,20 @@ fn is_time_detect(emoji: &ReactionType) -> bool {
         } if name == TIME_DETECT_EMOJI
     )
 }
+
+fn is_delete(emoji: &ReactionType) -> bool {
+    matches!(
+        emoji,
+        ReactionType::Unicode {
+            name
+        } if name == DELETE_EMOJI
+    )
+}
+
+pub fn notes_description(notes: &[LocalTimeNote]) -> String {
+    notes
+        .iter()
+        .map(|note| note.description())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use sparkle_convenience::{
+    error::{extract::HttpErrorExt, ErrorExt},
+    message::HttpExt,
+};
+
+use crate::{database::Reminder, embed, message::UNKNOWN_CHANNEL_ERROR_CODE, Context};
+
+const UNKNOWN_USER_ERROR_CODE: u64 = 10013;
+
+// trying again later won't help, like when the user and the channel are both unreachable
+fn is_permanent_failure(err: &anyhow::Error) -> bool {
+    err.user().is_some()
+        || matches!(
+            err.downcast_ref::<twilight_http::Error>()
+                .and_then(HttpErrorExt::code),
+            Some(UNKNOWN_CHANNEL_ERROR_CODE | UNKNOWN_USER_ERROR_CODE)
+        )
+}
+
+impl Context {
+    pub async fn send_due_reminders(&self) -> Result<()> {
+        for reminder in self.due_reminders().await? {
+            match self.send_reminder(reminder).await {
+                // keep it to try again next time
+                Err(err) if !is_permanent_failure(&err) => self.bot.log(err).await,
+                _ => self.delete_reminder(reminder).await?,
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn send_reminder(&self, reminder: Reminder) -> Result<()> {
+        let message_url = format!(
+            "https://discord.com/channels/{}/{}/{}",
+            reminder
+                .guild_id
+                .map_or_else(|| "@me".to_owned(), |guild_id| guild_id.to_string()),
+            reminder.channel_id,
+            reminder.message_id
+        );
+        let embeds = [embed()
+            .title("⏰ it's time!")
+            .description(format!(
+                "you asked me to remind you about [this message]({message_url})"
+            ))
+            .build()];
+
+        let dm_result = async {
+            self.bot
+                .http
+                .dm_user(reminder.user_id)
+                .await?
+                .embeds(&embeds)?
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+
+        // if their dms are closed, pinging them where they asked is the next best thing
+        if dm_result.is_err() {
+            self.bot
+                .http
+                .create_message(reminder.channel_id)
+                .content(&format!("<@{}>", reminder.user_id))?
+                .embeds(&embeds)?
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,10 +1,14 @@
 use std::ops::Range;
 
 use anyhow::Result;
-use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
-use chrono_tz::Tz;
+use chrono::{
+    DateTime, Datelike, Duration, DurationRound, LocalResult, Months, NaiveDate, NaiveDateTime,
+    NaiveTime, Offset, TimeZone, Timelike, Utc, Weekday,
+};
+use chrono_tz::{OffsetComponents, Tz, TZ_VARIANTS};
 use lazy_regex::{lazy_regex, Captures, Lazy, Regex};
 use sparkle_convenience::error::IntoError;
+use twilight_interactions::command::{CommandOption, CreateOption};
 use twilight_model::id::{marker::UserMarker, Id};
 
 use crate::{
@@ -16,6 +20,318 @@ static REGEX_24_HOUR: Lazy<Regex> = lazy_regex!(r#"\b([0-1]?[0-9]|2[0-3]):([0-5]
 static REGEX_12_HOUR: Lazy<Regex> = lazy_regex!(r#"\b(1[0-2]|0?[1-9]) ?([AaPp][Mm])\b"#);
 static REGEX_12_HOUR_WITH_MIN: Lazy<Regex> =
     lazy_regex!(r#"\b(1[0-2]|0?[1-9]):([0-5][0-9]) ?([AaPp][Mm])\b"#);
+static REGEX_ISO_DATE: Lazy<Regex> = lazy_regex!(r#"\b(\d{4})-(\d{1,2})-(\d{1,2})\b"#);
+static REGEX_DAY_MONTH: Lazy<Regex> = lazy_regex!(
+    r#"\b(\d{1,2})(?:st|nd|rd|th)? (jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*(?:,? (\d{4}))?\b"#
+);
+static REGEX_MONTH_DAY: Lazy<Regex> = lazy_regex!(
+    r#"\b(jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]* (\d{1,2})(?:st|nd|rd|th)?(?:,? (\d{4}))?\b"#
+);
+static REGEX_DAY_WORD: Lazy<Regex> = lazy_regex!(r#"\b(today|tonight|tomorrow|yesterday)\b"#);
+static REGEX_WEEKDAY: Lazy<Regex> =
+    lazy_regex!(r#"\b(next )?(mon|tues|wednes|thurs|fri|satur|sun)day\b"#);
+static REGEX_RELATIVE: Lazy<Regex> =
+    lazy_regex!(r#"\bin (\d+|an?) (min|minute|hour|day|week)s?\b"#);
+static REGEX_NOON_MIDNIGHT: Lazy<Regex> = lazy_regex!(r#"\b(noon|midnight)\b"#);
+static REGEX_TIMESTAMP: Lazy<Regex> = lazy_regex!(r#"<t:(-?\d+)(?::[tTdDfFR])?>"#);
+// what format_range writes for a date with an end
+static REGEX_TIMESTAMP_RANGE: Lazy<Regex> =
+    lazy_regex!(r#"<t:(-?\d+)(?::[tTdDfFR])?>(?: – <t:(-?\d+)(?::[tTdDfFR])?>)?"#);
+static REGEX_OFFSET: Lazy<Regex> =
+    lazy_regex!(r#"^(?:utc|gmt)? ?([+-])(\d{1,2})(?::?([0-5]\d))?$"#);
+// abbreviations and places people often type that aren't in a timezone name
+const LOCATION_ALIASES: &[(&str, Tz)] = &[
+    ("est", Tz::America__New_York),
+    ("edt", Tz::America__New_York),
+    ("eastern", Tz::America__New_York),
+    ("cst", Tz::America__Chicago),
+    ("cdt", Tz::America__Chicago),
+    ("central", Tz::America__Chicago),
+    ("mst", Tz::America__Denver),
+    ("mdt", Tz::America__Denver),
+    ("mountain", Tz::America__Denver),
+    ("pst", Tz::America__Los_Angeles),
+    ("pdt", Tz::America__Los_Angeles),
+    ("pacific", Tz::America__Los_Angeles),
+    ("san francisco", Tz::America__Los_Angeles),
+    ("seattle", Tz::America__Los_Angeles),
+    ("boston", Tz::America__New_York),
+    ("washington", Tz::America__New_York),
+    ("bst", Tz::Europe__London),
+    ("uk", Tz::Europe__London),
+    ("cet", Tz::Europe__Paris),
+    ("cest", Tz::Europe__Paris),
+    ("eet", Tz::Europe__Athens),
+    ("eest", Tz::Europe__Athens),
+    ("ist", Tz::Asia__Kolkata),
+    ("india", Tz::Asia__Kolkata),
+    ("mumbai", Tz::Asia__Kolkata),
+    ("delhi", Tz::Asia__Kolkata),
+    ("bangalore", Tz::Asia__Kolkata),
+    ("china", Tz::Asia__Shanghai),
+    ("beijing", Tz::Asia__Shanghai),
+    ("jst", Tz::Asia__Tokyo),
+    ("japan", Tz::Asia__Tokyo),
+    ("kst", Tz::Asia__Seoul),
+    ("korea", Tz::Asia__Seoul),
+    ("aest", Tz::Australia__Sydney),
+    ("aedt", Tz::Australia__Sydney),
+    ("awst", Tz::Australia__Perth),
+    ("nzst", Tz::Pacific__Auckland),
+    ("nzdt", Tz::Pacific__Auckland),
+    ("hst", Tz::Pacific__Honolulu),
+    ("hawaii", Tz::Pacific__Honolulu),
+];
+
+static REGEX_PAST_TENSE: Lazy<Regex> = lazy_regex!(
+    r#"(?i)\b(was|were|had|did|ago|earlier|already|yesterday|last|started|ended|happened|finished)\b"#
+);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, CommandOption, CreateOption, sqlx::Type)]
+#[sqlx(type_name = "time_resolution")]
+pub enum TimeResolution {
+    #[default]
+    #[option(name = "always today", value = "today")]
+    Today,
+    #[option(
+        name = "whichever is closest to now, even if it's yesterday or tomorrow",
+        value = "nearest"
+    )]
+    Nearest,
+    #[option(
+        name = "the next time it happens, unless the message talks about the past",
+        value = "next"
+    )]
+    Next,
+}
+
+impl TimeResolution {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Today => "times in messages are always converted for today",
+            Self::Nearest => {
+                "times in messages are converted for whichever day makes them closest to now, so \
+                 12:30 AM said at 11:50 PM is tomorrow"
+            }
+            Self::Next => {
+                "times in messages are converted for the next time they happen, so 8 PM said at 11 \
+                 PM is tomorrow, unless the message talks about the past"
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, CommandOption, CreateOption, sqlx::Type)]
+#[sqlx(type_name = "dst_policy")]
+pub enum DstPolicy {
+    #[default]
+    #[option(
+        name = "move skipped times forward, use the first of repeated times",
+        value = "shift_forward"
+    )]
+    ShiftForward,
+    #[option(name = "always use the earlier time", value = "earliest")]
+    Earliest,
+    #[option(name = "always use the later time", value = "latest")]
+    Latest,
+}
+
+impl DstPolicy {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::ShiftForward => {
+                "times the clocks skip are moved forward, times that happen twice use the first one"
+            }
+            Self::Earliest => {
+                "times the clocks skip are moved back, times that happen twice use the first one"
+            }
+            Self::Latest => {
+                "times the clocks skip are moved forward, times that happen twice use the second \
+                 one"
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CommandOption, CreateOption, sqlx::Type)]
+#[sqlx(type_name = "days_off")]
+pub enum DaysOff {
+    #[option(name = "saturday and sunday", value = "saturday_sunday")]
+    SaturdaySunday,
+    #[option(name = "friday and saturday", value = "friday_saturday")]
+    FridaySaturday,
+    #[option(name = "just sunday", value = "sunday")]
+    Sunday,
+    #[option(name = "no days off", value = "never")]
+    Never,
+}
+
+impl DaysOff {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::SaturdaySunday => "off on saturday and sunday",
+            Self::FridaySaturday => "off on friday and saturday",
+            Self::Sunday => "off on sunday",
+            Self::Never => "no days off",
+        }
+    }
+
+    const fn contains(self, weekday: Weekday) -> bool {
+        matches!(
+            (self, weekday),
+            (Self::SaturdaySunday, Weekday::Sat | Weekday::Sun)
+                | (Self::FridaySaturday, Weekday::Fri | Weekday::Sat)
+                | (Self::Sunday, Weekday::Sun)
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorkingHours {
+    pub start: u32,
+    pub end: u32,
+    pub days_off: DaysOff,
+}
+
+impl WorkingHours {
+    pub fn is_working(self, local: DateTime<Tz>) -> bool {
+        !self.days_off.contains(local.weekday())
+            && is_working_hour(local.hour(), self.start, self.end)
+    }
+
+    pub fn description(self) -> String {
+        format!(
+            "{}:00 – {}:00, {}",
+            self.start,
+            self.end,
+            self.days_off.description()
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Birthday {
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Birthday {
+    pub fn new(month: u32, day: u32) -> Result<Self> {
+        // 2000 is a leap year, so february 29 is allowed
+        NaiveDate::from_ymd_opt(2000, month, day).ok_or(CustomError::BadDate)?;
+        Ok(Self { month, day })
+    }
+
+    pub fn is_on(self, date: NaiveDate) -> bool {
+        // february 29 birthdays are celebrated on february 28 in other years
+        NaiveDate::from_ymd_opt(date.year(), self.month, self.day)
+            .or_else(|| NaiveDate::from_ymd_opt(date.year(), self.month, self.day - 1))
+            == Some(date)
+    }
+
+    pub fn description(self) -> String {
+        NaiveDate::from_ymd_opt(2000, self.month, self.day).map_or_else(
+            || format!("{}/{}", self.month, self.day),
+            |date| date.format("%B %-d").to_string(),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, CommandOption, CreateOption)]
+pub enum Recurrence {
+    #[option(name = "every day", value = "daily")]
+    Daily,
+    #[option(name = "every week", value = "weekly")]
+    Weekly,
+    #[option(name = "every other week", value = "biweekly")]
+    Biweekly,
+    #[option(name = "every month", value = "monthly")]
+    Monthly,
+}
+
+impl Recurrence {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Daily => "every day",
+            Self::Weekly => "every week",
+            Self::Biweekly => "every other week",
+            Self::Monthly => "every month",
+        }
+    }
+
+    fn nth(self, start: NaiveDateTime, n: u32) -> Option<NaiveDateTime> {
+        match self {
+            Self::Daily => start.checked_add_signed(Duration::days(n.into())),
+            Self::Weekly => start.checked_add_signed(Duration::weeks(n.into())),
+            Self::Biweekly => start.checked_add_signed(Duration::weeks(i64::from(n) * 2)),
+            Self::Monthly => start.checked_add_months(Months::new(n)),
+        }
+    }
+
+    fn periods_between(self, start: NaiveDateTime, end: NaiveDateTime) -> u32 {
+        let periods = match self {
+            Self::Daily => (end - start).num_days(),
+            Self::Weekly => (end - start).num_weeks(),
+            Self::Biweekly => (end - start).num_weeks() / 2,
+            Self::Monthly => {
+                i64::from(end.year() - start.year()) * 12 + i64::from(end.month())
+                    - i64::from(start.month())
+                    - 1
+            }
+        };
+
+        periods
+            .clamp(0, u32::MAX.into())
+            .try_into()
+            .unwrap_or_default()
+    }
+
+    pub fn occurrences(
+        self,
+        start: DateTime<Tz>,
+        now: DateTime<Utc>,
+        count: usize,
+    ) -> Vec<DateTime<Tz>> {
+        let tz = start.timezone();
+        let start = start.naive_local();
+
+        (self.periods_between(start, now.with_timezone(&tz).naive_local())..)
+            .map_while(|n| self.nth(start, n))
+            .map(|local| resolve_local(tz, local, DstPolicy::default()).0)
+            // start has no fraction of a second, so it'd be before now if it's now
+            .filter(|occurrence| occurrence.timestamp() >= now.timestamp())
+            .take(count)
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LocalTimeNote {
+    Repeated(DstPolicy),
+    Skipped(DstPolicy),
+}
+
+impl LocalTimeNote {
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Repeated(DstPolicy::Latest) => {
+                "that time happens twice that day because the clocks go back, i used the second \
+                 one"
+            }
+            Self::Repeated(_) => {
+                "that time happens twice that day because the clocks go back, i used the first one"
+            }
+            Self::Skipped(DstPolicy::Earliest) => {
+                "that time doesn't exist that day because the clocks go forward, i moved it back by \
+                 the same amount"
+            }
+            Self::Skipped(_) => {
+                "that time doesn't exist that day because the clocks go forward, i moved it forward \
+                 by the same amount"
+            }
+        }
+    }
+}
 
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -23,6 +339,7 @@ pub struct ParsedTime {
     pub hour: u32,
     pub min: u32,
     pub range: Range<usize>,
+    pub is_past: bool,
 }
 
 impl ParsedTime {
@@ -56,6 +373,7 @@ impl ParsedTime {
                     hour: to_24_hour(hour, am_pm)?,
                     min,
                     range: Self::range(&captures)?,
+                    is_past: is_past_tense(s, &captures)?,
                 })
             })
             .collect()
@@ -71,6 +389,7 @@ impl ParsedTime {
                     hour: to_24_hour(hour, am_pm)?,
                     min: 0,
                     range: Self::range(&captures)?,
+                    is_past: is_past_tense(s, &captures)?,
                 })
             })
             .collect()
@@ -86,6 +405,7 @@ impl ParsedTime {
                     hour,
                     min,
                     range: Self::range(&captures)?,
+                    is_past: is_past_tense(s, &captures)?,
                 })
             })
             .collect()
@@ -94,35 +414,93 @@ impl ParsedTime {
     fn range(captures: &Captures<'_>) -> Result<Range<usize>> {
         Ok(captures.get(0).ok()?.range())
     }
+
+    pub fn resolve(
+        &self,
+        tz: Tz,
+        now: DateTime<Utc>,
+        resolution: TimeResolution,
+        policy: DstPolicy,
+    ) -> Result<(DateTime<Tz>, Option<LocalTimeNote>)> {
+        let time = NaiveTime::from_hms_opt(self.hour, self.min, 0).ok()?;
+        let today = now.with_timezone(&tz).date_naive();
+
+        Ok(match resolution {
+            TimeResolution::Today => resolve_local(tz, today.and_time(time), policy),
+            TimeResolution::Nearest => [today.pred_opt(), Some(today), today.succ_opt()]
+                .into_iter()
+                .flatten()
+                .map(|date| resolve_local(tz, date.and_time(time), policy))
+                .min_by_key(|(date_time, _)| (date_time.timestamp() - now.timestamp()).abs())
+                .ok()?,
+            TimeResolution::Next => {
+                let resolved_today = resolve_local(tz, today.and_time(time), policy);
+                if self.is_past || resolved_today.0 > now {
+                    resolved_today
+                } else {
+                    resolve_local(tz, today.succ_opt().ok()?.and_time(time), policy)
+                }
+            }
+        })
+    }
 }
 
 impl Context {
     pub async fn user_time(
         &self,
         user_id: Id<UserMarker>,
-        date: date::Command,
-    ) -> Result<DateTime<Tz>> {
-        let Some(tz) = self.timezone(user_id).await? else {
-            return Err(CustomError::MissingTimezone(self.command_ids.timezone).into());
+        date: &date::Command,
+    ) -> Result<(DateTime<Tz>, Option<LocalTimeNote>)> {
+        let tz = if let Some(timezone) = &date.timezone {
+            tz_from_location(timezone, Utc::now()).ok_or(CustomError::BadTimezone)?
+        } else if let Some(other_user_id) = date.user {
+            self.timezone(other_user_id)
+                .await?
+                .ok_or(CustomError::OtherUserMissingTimezone(
+                    self.command_ids.timezone,
+                ))?
+        } else {
+            self.timezone(user_id)
+                .await?
+                .ok_or(CustomError::MissingTimezone(self.command_ids.timezone))?
+        };
+
+        let now = Utc::now().with_timezone(&tz).naive_local();
+        let base = match &date.when {
+            Some(when) => parse_when(when, now)?.ok_or(CustomError::BadWhen)?,
+            None => now,
         };
 
-        let now = Utc::now().with_timezone(&tz);
-        Ok(tz
-            .with_ymd_and_hms(
-                date.year
-                    .map_or_else(|| Ok(now.year()), TryInto::try_into)?,
-                date.month
-                    .map_or_else(|| Ok(now.month()), |month| month.value().try_into())?,
-                date.day.map_or_else(|| Ok(now.day()), TryInto::try_into)?,
-                date.hour
-                    .map_or_else(|| Ok(now.hour()), TryInto::try_into)?,
-                date.minute
-                    .map_or_else(|| Ok(now.minute()), TryInto::try_into)?,
-                date.second
-                    .map_or_else(|| Ok(now.second()), TryInto::try_into)?,
+        let local = NaiveDate::from_ymd_opt(
+            date.year
+                .map_or_else(|| Ok(base.year()), TryInto::try_into)?,
+            date.month
+                .map_or_else(|| Ok(base.month()), |month| month.value().try_into())?,
+            date.day.map_or_else(|| Ok(base.day()), TryInto::try_into)?,
+        )
+        .and_then(|day| {
+            Some(
+                day.and_hms_opt(
+                    date.hour
+                        .map_or_else(|| Ok(base.hour()), TryInto::try_into)
+                        .ok()?,
+                    date.minute
+                        .map_or_else(|| Ok(base.minute()), TryInto::try_into)
+                        .ok()?,
+                    date.second
+                        .map_or_else(|| Ok(base.second()), TryInto::try_into)
+                        .ok()?,
+                ),
             )
-            .single()
-            .ok_or(CustomError::BadDate)?)
+        })
+        .flatten()
+        .ok_or(CustomError::BadDate)?;
+
+        Ok(resolve_local(
+            tz,
+            local,
+            self.user_dst_policy(user_id).await?,
+        ))
     }
 }
 
@@ -134,6 +512,340 @@ pub fn format(time: DateTime<Tz>, style: Option<Style>) -> String {
     )
 }
 
+pub fn format_range(
+    start: DateTime<Tz>,
+    end: Option<DateTime<Tz>>,
+    style: Option<Style>,
+    extra_style: Option<Style>,
+) -> String {
+    let end = end.map_or_else(String::new, |end| {
+        let end_style = if end.date_naive() == start.date_naive() {
+            Some(Style::ShortTime)
+        } else {
+            style
+        };
+        format!(" – {}", format(end, end_style))
+    });
+    let extra = extra_style.map_or_else(String::new, |extra_style| {
+        format!(" ({})", format(start, Some(extra_style)))
+    });
+
+    format!("{}{end}{extra}", format(start, style))
+}
+
+#[derive(Clone, Debug)]
+pub struct OffsetGroup {
+    pub offset: i32,
+    pub members: Vec<(Id<UserMarker>, Tz)>,
+}
+
+pub fn group_by_offset(
+    mut timezones: Vec<(Id<UserMarker>, Tz)>,
+    now: DateTime<Utc>,
+) -> Vec<OffsetGroup> {
+    timezones.sort_by_key(|(_, tz)| (utc_offset(*tz, now), tz.name()));
+
+    let mut groups: Vec<OffsetGroup> = vec![];
+    for (user_id, tz) in timezones {
+        let offset = utc_offset(tz, now);
+        match groups.last_mut() {
+            Some(group) if group.offset == offset => group.members.push((user_id, tz)),
+            _ => groups.push(OffsetGroup {
+                offset,
+                members: vec![(user_id, tz)],
+            }),
+        }
+    }
+
+    groups
+}
+
+pub const fn is_working_hour(hour: u32, start_hour: u32, end_hour: u32) -> bool {
+    if start_hour <= end_hour {
+        hour >= start_hour && hour < end_hour
+    } else {
+        hour >= start_hour || hour < end_hour
+    }
+}
+
+pub fn utc_offset(tz: Tz, now: DateTime<Utc>) -> i32 {
+    tz.offset_from_utc_datetime(&now.naive_utc())
+        .fix()
+        .local_minus_utc()
+}
+
+pub fn format_offset(offset: i32) -> String {
+    format!(
+        "UTC{}{:02}:{:02}",
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 3600,
+        offset.abs() % 3600 / 60
+    )
+}
+
+pub fn is_dst(tz: Tz, now: DateTime<Utc>) -> bool {
+    !tz.offset_from_utc_datetime(&now.naive_utc())
+        .dst_offset()
+        .is_zero()
+}
+
+pub fn next_offset_change(tz: Tz, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let offset = utc_offset(tz, now);
+
+    let mut after = (1..=366)
+        .map(|day| now + Duration::days(day))
+        .find(|time| utc_offset(tz, *time) != offset)?;
+    let mut before = after - Duration::days(1);
+    while after - before > Duration::minutes(1) {
+        let middle = before + (after - before) / 2;
+        if utc_offset(tz, middle) == offset {
+            before = middle;
+        } else {
+            after = middle;
+        }
+    }
+
+    after.duration_trunc(Duration::minutes(1)).ok()
+}
+
+pub fn format_offset_difference(difference: i32) -> String {
+    if difference == 0 {
+        return "same time as you".to_owned();
+    }
+
+    let hours = difference.abs() / 3600;
+    let minutes = difference.abs() % 3600 / 60;
+
+    let mut parts = vec![];
+    match hours {
+        0 => {}
+        1 => parts.push("1 hour".to_owned()),
+        hours => parts.push(format!("{hours} hours")),
+    }
+    if minutes != 0 {
+        parts.push(format!("{minutes} minutes"));
+    }
+    parts.push(
+        if difference > 0 {
+            "ahead of you"
+        } else {
+            "behind you"
+        }
+        .to_owned(),
+    );
+
+    parts.join(" ")
+}
+
+// timestamps too big to parse aren't something i put there, so they're skipped
+pub fn timestamps_from_text(s: &str) -> Vec<i64> {
+    REGEX_TIMESTAMP
+        .captures_iter(s)
+        .filter_map(|captures| captures[1].parse().ok())
+        .collect()
+}
+
+pub fn timestamp_ranges_from_text(s: &str) -> Vec<(i64, Option<i64>)> {
+    REGEX_TIMESTAMP_RANGE
+        .captures_iter(s)
+        .filter_map(|captures| {
+            let start = captures[1].parse().ok()?;
+            let end = captures
+                .get(2)
+                .and_then(|end| end.as_str().parse().ok())
+                .filter(|end| *end > start);
+            Some((start, end))
+        })
+        .collect()
+}
+
+// turns the timestamps i put in converted messages back into times people can type
+pub fn plain_times_from_text(s: &str, tz: Tz) -> String {
+    REGEX_TIMESTAMP
+        .replace_all(s, |captures: &Captures<'_>| {
+            captures[1]
+                .parse()
+                .ok()
+                .and_then(|timestamp| tz.timestamp_opt(timestamp, 0).single())
+                .map_or_else(
+                    || captures[0].to_owned(),
+                    |time| time.format("%-I:%M %p").to_string(),
+                )
+        })
+        .into_owned()
+}
+
+pub fn parse_when(s: &str, now: NaiveDateTime) -> Result<Option<NaiveDateTime>> {
+    let s = s.to_lowercase();
+    let today = now.date();
+    let mut is_parsed = false;
+
+    let mut date = None;
+    if let Some(captures) = REGEX_ISO_DATE.captures(&s) {
+        date = Some(
+            NaiveDate::from_ymd_opt(
+                captures[1].parse()?,
+                captures[2].parse()?,
+                captures[3].parse()?,
+            )
+            .ok_or(CustomError::BadDate)?,
+        );
+    } else if let Some(captures) = REGEX_DAY_MONTH.captures(&s) {
+        date = Some(
+            NaiveDate::from_ymd_opt(
+                captures
+                    .get(3)
+                    .map_or_else(|| Ok(today.year()), |year| year.as_str().parse())?,
+                month_from_name(&captures[2]).ok()?,
+                captures[1].parse()?,
+            )
+            .ok_or(CustomError::BadDate)?,
+        );
+    } else if let Some(captures) = REGEX_MONTH_DAY.captures(&s) {
+        date = Some(
+            NaiveDate::from_ymd_opt(
+                captures
+                    .get(3)
+                    .map_or_else(|| Ok(today.year()), |year| year.as_str().parse())?,
+                month_from_name(&captures[1]).ok()?,
+                captures[2].parse()?,
+            )
+            .ok_or(CustomError::BadDate)?,
+        );
+    } else if let Some(captures) = REGEX_DAY_WORD.captures(&s) {
+        date = match &captures[1] {
+            "tomorrow" => today.succ_opt(),
+            "yesterday" => today.pred_opt(),
+            _ => Some(today),
+        };
+    } else if let Some(captures) = REGEX_WEEKDAY.captures(&s) {
+        let weekday = match &captures[2] {
+            "mon" => 0,
+            "tues" => 1,
+            "wednes" => 2,
+            "thurs" => 3,
+            "fri" => 4,
+            "satur" => 5,
+            _ => 6,
+        };
+        let mut days_ahead = (weekday + 7 - today.weekday().num_days_from_monday()) % 7;
+        if days_ahead == 0 && captures.get(1).is_some() {
+            days_ahead = 7;
+        }
+        date = Some(today + Duration::days(days_ahead.into()));
+    }
+    is_parsed |= date.is_some();
+
+    let mut shift = Duration::zero();
+    if let Some(captures) = REGEX_RELATIVE.captures(&s) {
+        let amount: i64 = match &captures[1] {
+            "a" | "an" => 1,
+            amount => amount.parse().map_err(|_| CustomError::BadWhen)?,
+        };
+        let unit = match &captures[2] {
+            "min" | "minute" => Duration::minutes(1),
+            "hour" => Duration::hours(1),
+            "day" => Duration::days(1),
+            _ => Duration::weeks(1),
+        };
+        let seconds = unit
+            .num_seconds()
+            .checked_mul(amount)
+            .filter(|seconds| *seconds <= Duration::max_value().num_seconds())
+            .ok_or(CustomError::BadWhen)?;
+        shift = Duration::seconds(seconds);
+        is_parsed = true;
+    }
+
+    let time = if let Some(captures) = REGEX_NOON_MIDNIGHT.captures(&s) {
+        NaiveTime::from_hms_opt(if &captures[1] == "noon" { 12 } else { 0 }, 0, 0)
+    } else {
+        ParsedTime::all_from_text(&s)?
+            .first()
+            .and_then(|time| NaiveTime::from_hms_opt(time.hour, time.min, 0))
+    };
+    is_parsed |= time.is_some();
+
+    if !is_parsed {
+        return Ok(None);
+    }
+
+    Ok(Some(
+        date.unwrap_or(today)
+            .and_time(time.unwrap_or_else(|| now.time()))
+            .checked_add_signed(shift)
+            .ok_or(CustomError::BadWhen)?,
+    ))
+}
+
+fn month_from_name(name: &str) -> Option<u32> {
+    Some(match name {
+        "jan" => 1,
+        "feb" => 2,
+        "mar" => 3,
+        "apr" => 4,
+        "may" => 5,
+        "jun" => 6,
+        "jul" => 7,
+        "aug" => 8,
+        "sep" => 9,
+        "oct" => 10,
+        "nov" => 11,
+        "dec" => 12,
+        _ => return None,
+    })
+}
+
+fn is_past_tense(s: &str, captures: &Captures<'_>) -> Result<bool> {
+    const CLAUSE_SEPARATORS: &[char] = &['.', ',', ';', '!', '?', '\n'];
+
+    let range = captures.get(0).ok()?.range();
+    let clause_start = s
+        .get(..range.start)
+        .ok()?
+        .rfind(CLAUSE_SEPARATORS)
+        .map_or(0, |index| index + 1);
+    let clause_end = s
+        .get(range.end..)
+        .ok()?
+        .find(CLAUSE_SEPARATORS)
+        .map_or(s.len(), |index| range.end + index);
+
+    Ok(REGEX_PAST_TENSE.is_match(s.get(clause_start..clause_end).ok()?))
+}
+
+pub fn resolve_local(
+    tz: Tz,
+    local: NaiveDateTime,
+    policy: DstPolicy,
+) -> (DateTime<Tz>, Option<LocalTimeNote>) {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(date_time) => (date_time, None),
+        LocalResult::Ambiguous(earliest, latest) => (
+            if policy == DstPolicy::Latest {
+                latest
+            } else {
+                earliest
+            },
+            Some(LocalTimeNote::Repeated(policy)),
+        ),
+        LocalResult::None => {
+            let offset = if policy == DstPolicy::Earliest {
+                tz.offset_from_utc_datetime(&(local + Duration::days(1)))
+            } else {
+                tz.offset_from_utc_datetime(&(local - Duration::days(1)))
+            }
+            .fix();
+            let utc = local - Duration::seconds(offset.local_minus_utc().into());
+
+            (
+                tz.from_utc_datetime(&utc),
+                Some(LocalTimeNote::Skipped(policy)),
+            )
+        }
+    }
+}
+
 pub fn tz_from_locale(locale: &str) -> Option<Tz> {
     Some(match locale {
         "da" | "de" => Tz::Europe__Berlin,
@@ -162,6 +874,48 @@ pub fn tz_from_locale(locale: &str) -> Option<Tz> {
     })
 }
 
+pub fn tz_from_location(location: &str, now: DateTime<Utc>) -> Option<Tz> {
+    let location = location.trim().to_lowercase();
+    if location == "utc" || location == "gmt" {
+        return Some(Tz::UTC);
+    }
+
+    if let Some(captures) = REGEX_OFFSET.captures(&location) {
+        let sign = if &captures[1] == "-" { -1 } else { 1 };
+        let hours: i32 = captures[2].parse().ok()?;
+        let minutes: i32 = captures
+            .get(3)
+            .map_or("0", |minutes| minutes.as_str())
+            .parse()
+            .ok()?;
+        if minutes == 0 {
+            // the etc zones have their signs flipped, so utc+3 is etc/gmt-3
+            if let Ok(tz) = format!("Etc/GMT{:+}", -sign * hours).parse() {
+                return Some(tz);
+            }
+        }
+        let offset = sign * (hours * 3600 + minutes * 60);
+        return TZ_VARIANTS
+            .iter()
+            .copied()
+            .find(|tz| utc_offset(*tz, now) == offset);
+    }
+
+    if let Some((_, tz)) = LOCATION_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == location)
+    {
+        return Some(*tz);
+    }
+
+    let location = location.replace(' ', "_");
+    let names = || TZ_VARIANTS.iter().map(|tz| (*tz, tz.name().to_lowercase()));
+    names()
+        .find(|(_, name)| *name == location)
+        .or_else(|| names().find(|(_, name)| name.rsplit('/').next() == Some(&location)))
+        .map(|(tz, _)| tz)
+}
+
 fn to_24_hour(hour: u32, am_pm: &str) -> Result<u32> {
     Ok(match am_pm.to_ascii_lowercase().as_str() {
         "am" => {
@@ -187,3 +941,300 @@ fn to_24_hour(hour: u32, am_pm: &str) -> Result<u32> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+
+    use super::*;
+
+    fn berlin(month: u32, day: u32, hour: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, month, day)
+            .unwrap()
+            .and_hms_opt(hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn resolve_local_skipped() {
+        // clocks go from 2:00 to 3:00 on 2023-03-26 in berlin
+        let local = berlin(3, 26, 2, 30);
+        let (time, note) = resolve_local(Tz::Europe__Berlin, local, DstPolicy::ShiftForward);
+        assert_eq!(time.naive_local(), berlin(3, 26, 3, 30));
+        assert_eq!(note, Some(LocalTimeNote::Skipped(DstPolicy::ShiftForward)));
+
+        let (time, _) = resolve_local(Tz::Europe__Berlin, local, DstPolicy::Latest);
+        assert_eq!(time.naive_local(), berlin(3, 26, 3, 30));
+
+        let (time, note) = resolve_local(Tz::Europe__Berlin, local, DstPolicy::Earliest);
+        assert_eq!(time.naive_local(), berlin(3, 26, 1, 30));
+        assert_eq!(note, Some(LocalTimeNote::Skipped(DstPolicy::Earliest)));
+    }
+
+    #[test]
+    fn resolve_local_repeated() {
+        // clocks go from 3:00 back to 2:00 on 2023-10-29 in berlin
+        let local = berlin(10, 29, 2, 30);
+        let (time, note) = resolve_local(Tz::Europe__Berlin, local, DstPolicy::ShiftForward);
+        assert_eq!(time.timestamp(), 1_698_539_400);
+        assert_eq!(note, Some(LocalTimeNote::Repeated(DstPolicy::ShiftForward)));
+
+        let (time, _) = resolve_local(Tz::Europe__Berlin, local, DstPolicy::Earliest);
+        assert_eq!(time.timestamp(), 1_698_539_400);
+
+        let (time, note) = resolve_local(Tz::Europe__Berlin, local, DstPolicy::Latest);
+        assert_eq!(time.timestamp(), 1_698_543_000);
+        assert_eq!(note, Some(LocalTimeNote::Repeated(DstPolicy::Latest)));
+
+        let (_, note) = resolve_local(Tz::Europe__Berlin, berlin(10, 29, 4, 0), DstPolicy::Latest);
+        assert_eq!(note, None);
+    }
+
+    #[test]
+    fn parsed_time_resolve_across_dst() {
+        let parsed_time = ParsedTime {
+            hour: 2,
+            min: 30,
+            range: 0..4,
+            is_past: false,
+        };
+        let now = Tz::Europe__Berlin
+            .from_local_datetime(&berlin(3, 26, 0, 0))
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let (time, note) = parsed_time
+            .resolve(
+                Tz::Europe__Berlin,
+                now,
+                TimeResolution::Today,
+                DstPolicy::default(),
+            )
+            .unwrap();
+        assert_eq!(time.naive_local(), berlin(3, 26, 3, 30));
+        assert_eq!(note, Some(LocalTimeNote::Skipped(DstPolicy::ShiftForward)));
+
+        let (time, note) = parsed_time
+            .resolve(
+                Tz::Europe__Berlin,
+                now,
+                TimeResolution::Next,
+                DstPolicy::Earliest,
+            )
+            .unwrap();
+        assert_eq!(time.naive_local(), berlin(3, 26, 1, 30));
+        assert_eq!(note, Some(LocalTimeNote::Skipped(DstPolicy::Earliest)));
+    }
+
+    #[test]
+    fn parse_when_relative() {
+        // a thursday
+        let now = berlin(4, 13, 9, 15);
+        assert_eq!(
+            parse_when("in 90 minutes", now).unwrap(),
+            Some(berlin(4, 13, 10, 45))
+        );
+        assert_eq!(
+            parse_when("in an hour", now).unwrap(),
+            Some(berlin(4, 13, 10, 15))
+        );
+        assert_eq!(
+            parse_when("tomorrow in 2 weeks", now).unwrap(),
+            Some(berlin(4, 28, 9, 15))
+        );
+        assert_eq!(parse_when("whenever", now).unwrap(), None);
+    }
+
+    #[test]
+    fn parse_when_weekday() {
+        let now = berlin(4, 13, 9, 15);
+        assert_eq!(
+            parse_when("friday 5pm", now).unwrap(),
+            Some(berlin(4, 14, 17, 0))
+        );
+        assert_eq!(
+            parse_when("thursday", now).unwrap(),
+            Some(berlin(4, 13, 9, 15))
+        );
+        assert_eq!(
+            parse_when("next thursday", now).unwrap(),
+            Some(berlin(4, 20, 9, 15))
+        );
+        assert_eq!(
+            parse_when("Monday 10:30", now).unwrap(),
+            Some(berlin(4, 17, 10, 30))
+        );
+    }
+
+    #[test]
+    fn parse_when_noon_midnight() {
+        let now = berlin(4, 13, 9, 15);
+        assert_eq!(parse_when("noon", now).unwrap(), Some(berlin(4, 13, 12, 0)));
+        assert_eq!(
+            parse_when("tomorrow midnight", now).unwrap(),
+            Some(berlin(4, 14, 0, 0))
+        );
+    }
+
+    #[test]
+    fn parse_when_overflow() {
+        let now = berlin(4, 13, 9, 15);
+        for when in [
+            "in 99999999999999999999 days",
+            "in 9999999999999999 weeks",
+            "in 999999999999 weeks",
+        ] {
+            assert_eq!(
+                parse_when(when, now)
+                    .unwrap_err()
+                    .downcast_ref::<CustomError>(),
+                Some(&CustomError::BadWhen),
+                "{when}"
+            );
+        }
+    }
+
+    #[test]
+    fn tz_from_location_names() {
+        let now = Utc::now();
+        assert_eq!(
+            tz_from_location("Europe/Berlin", now),
+            Some(Tz::Europe__Berlin)
+        );
+        assert_eq!(
+            tz_from_location("new york", now),
+            Some(Tz::America__New_York)
+        );
+        assert_eq!(tz_from_location(" TOKYO ", now), Some(Tz::Asia__Tokyo));
+        assert_eq!(tz_from_location("utc", now), Some(Tz::UTC));
+    }
+
+    #[test]
+    fn tz_from_location_aliases() {
+        let now = Utc::now();
+        assert_eq!(tz_from_location("PST", now), Some(Tz::America__Los_Angeles));
+        assert_eq!(tz_from_location("ist", now), Some(Tz::Asia__Kolkata));
+        assert_eq!(
+            tz_from_location("San Francisco", now),
+            Some(Tz::America__Los_Angeles)
+        );
+        for (alias, _) in LOCATION_ALIASES {
+            assert_eq!(alias.to_lowercase(), *alias);
+        }
+    }
+
+    #[test]
+    fn tz_from_location_not_found() {
+        let now = Utc::now();
+        assert_eq!(tz_from_location("ber", now), None);
+        assert_eq!(tz_from_location("europe", now), None);
+        assert_eq!(tz_from_location("atlantis", now), None);
+    }
+
+    fn berlin_time(month: u32, day: u32, hour: u32, min: u32) -> DateTime<Tz> {
+        Tz::Europe__Berlin
+            .from_local_datetime(&berlin(month, day, hour, min))
+            .unwrap()
+    }
+
+    #[test]
+    fn occurrences_start_now() {
+        let now = Utc::now();
+        let start = now
+            .with_timezone(&Tz::Europe__Berlin)
+            .duration_trunc(Duration::seconds(1))
+            .unwrap();
+        let occurrences = Recurrence::Daily.occurrences(start, now, 2);
+        assert_eq!(occurrences, [start, start + Duration::days(1)]);
+    }
+
+    #[test]
+    fn occurrences_weekly_across_dst() {
+        let start = berlin_time(3, 20, 9, 0);
+        let occurrences = Recurrence::Weekly.occurrences(start, start.with_timezone(&Utc), 3);
+        assert_eq!(
+            occurrences,
+            [start, berlin_time(3, 27, 9, 0), berlin_time(4, 3, 9, 0)]
+        );
+        assert_eq!(occurrences[1] - occurrences[0], Duration::hours(7 * 24 - 1));
+    }
+
+    #[test]
+    fn occurrences_biweekly_across_dst() {
+        let start = berlin_time(10, 23, 9, 0);
+        let now = berlin_time(10, 24, 0, 0).with_timezone(&Utc);
+        let occurrences = Recurrence::Biweekly.occurrences(start, now, 2);
+        assert_eq!(
+            occurrences,
+            [berlin_time(11, 6, 9, 0), berlin_time(11, 20, 9, 0)]
+        );
+    }
+
+    #[test]
+    fn occurrences_monthly_across_dst() {
+        let start = berlin_time(1, 31, 9, 0);
+        let now = berlin_time(2, 1, 0, 0).with_timezone(&Utc);
+        let occurrences = Recurrence::Monthly.occurrences(start, now, 3);
+        assert_eq!(
+            occurrences,
+            [
+                berlin_time(2, 28, 9, 0),
+                berlin_time(3, 31, 9, 0),
+                berlin_time(4, 30, 9, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn timestamps_skip_oversized() {
+        assert_eq!(
+            timestamps_from_text("<t:1681387200:t> or <t:99999999999999999999:t>"),
+            [1_681_387_200]
+        );
+    }
+
+    #[test]
+    fn timestamp_ranges() {
+        assert_eq!(
+            timestamp_ranges_from_text(
+                "<t:1681387200:F> – <t:1681390800:t> and <t:1681470000:F>, <t:1681470000:t> – \
+                 <t:1681466400:t>"
+            ),
+            [
+                (1_681_387_200, Some(1_681_390_800)),
+                (1_681_470_000, None),
+                (1_681_470_000, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn birthday_is_on() {
+        let birthday = Birthday::new(4, 13).unwrap();
+        assert!(birthday.is_on(NaiveDate::from_ymd_opt(2023, 4, 13).unwrap()));
+        assert!(!birthday.is_on(NaiveDate::from_ymd_opt(2023, 4, 14).unwrap()));
+        assert!(!birthday.is_on(NaiveDate::from_ymd_opt(2023, 3, 13).unwrap()));
+    }
+
+    #[test]
+    fn birthday_is_on_leap_day() {
+        let birthday = Birthday::new(2, 29).unwrap();
+        assert!(birthday.is_on(NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()));
+        assert!(!birthday.is_on(NaiveDate::from_ymd_opt(2024, 2, 28).unwrap()));
+        assert!(birthday.is_on(NaiveDate::from_ymd_opt(2023, 2, 28).unwrap()));
+        assert!(!birthday.is_on(NaiveDate::from_ymd_opt(2023, 3, 1).unwrap()));
+        assert!(Birthday::new(2, 30).is_err());
+    }
+
+    #[test]
+    fn plain_times_replace_timestamps() {
+        assert_eq!(
+            plain_times_from_text("lunch at <t:1681387200:t>, ok?", Tz::Europe__Berlin),
+            "lunch at 2:00 PM, ok?"
+        );
+        assert_eq!(
+            plain_times_from_text("<t:99999999999999999999:t>", Tz::UTC),
+            "<t:99999999999999999999:t>"
+        );
+    }
+}